@@ -0,0 +1,137 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::compare::{copy_dir_filtered, DirIgnore};
+
+/// Local, gitignored directory (created by `init`) holding snapshots of home files
+/// clobbered by a destructive sync, so `rollback` has something to restore from.
+pub const BACKUP_DIR: &str = ".backup";
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// One home-path file or directory backed up into a timestamped snapshot batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    /// The `~/...`-form path this backup was taken from, matching `TrackedFile::path`.
+    pub original_path: String,
+    /// Path to the snapshot, relative to the repo root (under `BACKUP_DIR/<timestamp>/`).
+    pub backup_path: PathBuf,
+    pub timestamp: u64,
+}
+
+/// Persisted record of every snapshot batch taken so far, so `rollback` can find the
+/// most recent (or a chosen) one without having to re-derive it from the filesystem.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BackupManifest {
+    entries: Vec<BackupEntry>,
+}
+
+impl BackupManifest {
+    fn manifest_path(repo_path: &Path) -> PathBuf {
+        repo_path.join(BACKUP_DIR).join(MANIFEST_FILE)
+    }
+
+    /// Loads the manifest, or starts a fresh empty one if it's missing or corrupt — a
+    /// bad manifest just means `rollback` has nothing to go on, not a hard failure.
+    pub fn load(repo_path: &Path) -> Self {
+        fs::read(Self::manifest_path(repo_path))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, repo_path: &Path) -> Result<()> {
+        let manifest_path = Self::manifest_path(repo_path);
+        if let Some(parent) = manifest_path.parent() {
+            fs::create_dir_all(parent).context("Failed to create .backup directory")?;
+        }
+        let contents = serde_json::to_vec_pretty(self).context("Failed to serialize backup manifest")?;
+        fs::write(&manifest_path, contents).context("Failed to write backup manifest")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every distinct snapshot timestamp, oldest first.
+    pub fn timestamps(&self) -> Vec<u64> {
+        let mut timestamps: Vec<u64> = self.entries.iter().map(|e| e.timestamp).collect();
+        timestamps.sort_unstable();
+        timestamps.dedup();
+        timestamps
+    }
+
+    /// The timestamp `rollback` should use: the most recent snapshot at or before
+    /// `at`, or the single most recent snapshot if `at` is `None`.
+    pub fn resolve_timestamp(&self, at: Option<u64>) -> Option<u64> {
+        let timestamps = self.timestamps();
+        match at {
+            Some(at) => timestamps.into_iter().rev().find(|&t| t <= at),
+            None => timestamps.into_iter().next_back(),
+        }
+    }
+
+    pub fn entries_at(&self, timestamp: u64) -> Vec<&BackupEntry> {
+        self.entries.iter().filter(|e| e.timestamp == timestamp).collect()
+    }
+
+    /// Snapshots `source` (a home-path file or directory about to be overwritten) into
+    /// this batch's timestamped subfolder, preserving its relative `~/...` layout, and
+    /// records the mapping.
+    pub fn backup(&mut self, repo_path: &Path, normalized_home_path: &str, source: &Path, timestamp: u64) -> Result<()> {
+        let relative = normalized_home_path.trim_start_matches("~/").trim_start_matches('/');
+        let backup_rel = PathBuf::from(BACKUP_DIR).join(timestamp.to_string()).join(relative);
+        let backup_abs = repo_path.join(&backup_rel);
+        if let Some(parent) = backup_abs.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if source.is_dir() {
+            copy_dir_filtered(source, &backup_abs, &DirIgnore::none(source))
+                .with_context(|| format!("Failed to back up directory {}", source.display()))?;
+        } else {
+            fs::copy(source, &backup_abs).with_context(|| format!("Failed to back up {}", source.display()))?;
+        }
+
+        self.entries.push(BackupEntry {
+            original_path: normalized_home_path.to_string(),
+            backup_path: backup_rel,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Deletes every snapshot batch older than the `keep` most recent, dropping their
+    /// manifest entries too. A `keep` of 0 is treated as "keep everything" rather than
+    /// wiping every snapshot, since that's almost certainly not what a caller means.
+    pub fn prune(&mut self, repo_path: &Path, keep: usize) -> Result<usize> {
+        if keep == 0 {
+            return Ok(0);
+        }
+
+        let timestamps = self.timestamps();
+        if timestamps.len() <= keep {
+            return Ok(0);
+        }
+
+        let to_prune: HashSet<u64> = timestamps[..timestamps.len() - keep].iter().copied().collect();
+        for &timestamp in &to_prune {
+            let dir = repo_path.join(BACKUP_DIR).join(timestamp.to_string());
+            let _ = fs::remove_dir_all(dir);
+        }
+
+        self.entries.retain(|e| !to_prune.contains(&e.timestamp));
+        Ok(to_prune.len())
+    }
+}
+
+/// Current unix timestamp, used both as the snapshot batch's directory name and as
+/// the manifest entries' `timestamp` field.
+pub fn now_timestamp() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
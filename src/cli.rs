@@ -15,15 +15,30 @@ pub enum Commands {
     Init {
         #[arg(help = "Path to initialize (defaults to current directory)")]
         path: Option<PathBuf>,
-        
+
         #[arg(long, help = "Tag for organizing custom configurations")]
         tag: Option<String>,
+
+        #[arg(long, help = "Git URL of the remote to save to config and register with git")]
+        remote: Option<String>,
     },
 
     #[command(about = "Add a config file using stub name or direct path")]
     Add {
         #[arg(help = "Stub names or paths (e.g., 'git', 'tmux', '~/.zshrc')")]
         stubs: Vec<String>,
+
+        #[arg(long, help = "Encrypt the added file(s) before storing them in the repo")]
+        encrypt: bool,
+
+        #[arg(long, help = "Password/seed phrase for encryption (prompted for if omitted)")]
+        password: Option<String>,
+
+        #[arg(long, help = "Glob pattern selecting files inside a tracked directory (repeatable)")]
+        include: Vec<String>,
+
+        #[arg(long, help = "Glob pattern excluding files inside a tracked directory (repeatable)")]
+        exclude: Vec<String>,
     },
 
     #[command(visible_aliases = ["rm"])]
@@ -38,7 +53,7 @@ pub enum Commands {
     List {
         #[arg(short, long, help = "Show all available stubs from database")]
         all: bool,
-        
+
         #[arg(help = "Filter by specific stub names (only works without --all)")]
         stubs: Vec<String>,
     },
@@ -50,30 +65,69 @@ pub enum Commands {
     Sync {
         #[arg(long, help = "Set dotfiles directory and save to local config")]
         dir: Option<PathBuf>,
+
+        #[arg(
+            long,
+            help = "Preview every step without touching the filesystem, git index, or remote"
+        )]
+        dry_run: bool,
+
+        #[arg(
+            long,
+            help = "Compare files by SHA-256 digest (printed on mismatch) instead of raw bytes"
+        )]
+        checksum: bool,
     },
 
+    #[command(about = "Abort an in-progress conflicted sync and restore the pre-sync state")]
+    SyncAbort,
+
+    #[command(about = "Show what conflicts remain in an in-progress sync")]
+    SyncStatus,
+
     #[command(about = "Sync from repository to home directory only")]
     SyncLocal,
 
+    #[command(about = "Restore a `.backup/` snapshot taken before a destructive restore/sync")]
+    Rollback {
+        #[arg(long, help = "Unix timestamp of the snapshot batch to restore (defaults to the most recent)")]
+        at: Option<u64>,
+    },
+
+    #[command(about = "Re-derive this repository's encryption key from your seed phrase")]
+    Unlock,
+
     #[command(about = "Pull changes from remote repository")]
     Pull,
 
     #[command(about = "Push changes to remote repository")]
     Push,
 
+    #[command(about = "Manage this repository's configured git remote")]
+    Remote {
+        #[command(subcommand)]
+        action: RemoteAction,
+    },
+
     #[command(about = "Create a new custom stub entry")]
     Create {
         #[arg(help = "Stub name for the new entry")]
         stub: String,
         #[arg(help = "File paths to track (relative to home directory)")]
         paths: Vec<String>,
-        
+
         #[arg(long, help = "Tag for organizing this custom stub")]
         tag: Option<String>,
     },
 
     #[command(about = "Scan system for available dotfiles and show their status")]
-    Scan,
+    Scan {
+        #[arg(
+            long,
+            help = "Keep running and live-update the dashboard as files change"
+        )]
+        watch: bool,
+    },
 
     #[command(about = "Change to dotfiles repository directory")]
     Cd,
@@ -83,18 +137,137 @@ pub enum Commands {
         #[command(subcommand)]
         action: ConfigAction,
     },
+
+    #[command(
+        about = "Mount tracked dotfiles as a read-only FUSE filesystem, decrypting lazily on read"
+    )]
+    Mount {
+        #[arg(help = "Directory to mount the virtual filesystem at")]
+        mountpoint: PathBuf,
+    },
+
+    #[command(visible_aliases = ["restore"])]
+    #[command(
+        about = "Restore tracked files from the repo into $HOME, backing up existing files first"
+    )]
+    Apply {
+        #[arg(help = "Filter by stub names or tracked paths (applies all tracked files if omitted)")]
+        stubs: Vec<String>,
+    },
+
+    #[command(about = "Export tracked history as a git bundle for offline transfer")]
+    Export {
+        #[arg(help = "Destination bundle file")]
+        file: PathBuf,
+        #[arg(
+            long,
+            help = "Attach a detached GPG signature over the bundle's SHA-256 (<file>.sig)"
+        )]
+        sign: bool,
+    },
+
+    #[command(about = "Import a git bundle produced by 'dotfiles export'")]
+    Import {
+        #[arg(help = "Bundle file to import")]
+        file: PathBuf,
+        #[arg(
+            long,
+            help = "Verify the accompanying <file>.sig detached GPG signature before importing"
+        )]
+        verify_signature: bool,
+    },
+
+    #[command(visible_aliases = ["daemon"])]
+    #[command(
+        about = "Watch tracked files and automatically run the full sync pipeline (import, commit, pull, export, push) when changes settle"
+    )]
+    Watch {
+        #[arg(long, help = "Minimum seconds between auto-syncs (defaults to 60)")]
+        interval: Option<u64>,
+    },
+
+    #[command(about = "Manage community/team remote stub catalogs merged into the stub database")]
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+
+    #[command(
+        about = "Import application-config definitions from a config source into the stub database"
+    )]
+    SyncDb {
+        #[arg(
+            long,
+            help = "Source to sync from (mackup, local); syncs every registered source if omitted"
+        )]
+        source: Option<String>,
+        #[arg(long, help = "Directory of .cfg files for the 'local' source")]
+        path: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Where to write the synced database (defaults to <repo>/synced_db)"
+        )]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum ConfigAction {
     #[command(about = "Set a configuration value")]
     Set {
-        #[arg(help = "Config field (use_xdg, repo_path, home_path, tag)")]
+        #[arg(
+            help = "Config field (use_xdg, repo_path, home_path, tag, git_backend, disable_git_hardening, merge_tool, encryption_backend, gpg_recipients, backup_retention, git_token)"
+        )]
         field: String,
         #[arg(help = "Value to set")]
         value: String,
     },
-    
+
     #[command(about = "Show current configuration")]
     Show,
 }
+
+#[derive(Subcommand)]
+pub enum RemoteAction {
+    #[command(about = "Set the remote URL in config and register it with git")]
+    Set {
+        #[arg(help = "Git URL of the remote")]
+        url: String,
+    },
+
+    #[command(about = "Show the configured remote URL")]
+    Get,
+
+    #[command(about = "Clear the configured remote URL (does not remove the git remote)")]
+    Unset,
+}
+
+#[derive(Subcommand)]
+pub enum DbAction {
+    #[command(about = "Add a remote stub catalog (git URL) to sync on 'db update'")]
+    AddRemote {
+        #[arg(help = "Git URL of the remote stub catalog")]
+        url: String,
+
+        #[arg(long, help = "Branch to clone/pull (defaults to the repo's default branch)")]
+        branch: Option<String>,
+
+        #[arg(long, help = "Glob pattern selecting which of the source's stubs to pull in (repeatable)")]
+        include: Vec<String>,
+
+        #[arg(long, help = "Glob pattern excluding stubs otherwise selected by --include (repeatable)")]
+        exclude: Vec<String>,
+    },
+
+    #[command(about = "Remove a previously added remote stub catalog")]
+    RemoveRemote {
+        #[arg(help = "Git URL of the remote stub catalog to remove")]
+        url: String,
+    },
+
+    #[command(about = "List configured remote stub catalogs")]
+    ListRemotes,
+
+    #[command(about = "Clone/pull every configured remote stub catalog into the local cache")]
+    Update,
+}
@@ -1,11 +1,27 @@
 use anyhow::{bail, Context, Result};
 use crate::config::{ConfigManager, TrackedFile};
 use crate::db::ConfigDatabase;
+use crate::dir_spec::{encrypted_member_name, DirSpec};
 use crate::encryption::FileEncryptor;
+use crate::manifest::NameManifest;
 use crate::sync::FileSyncer;
 use crate::utils::{print_success, print_error, print_info};
+use walkdir::WalkDir;
 
-pub fn execute(stubs_or_paths: Vec<String>, encrypt: bool, _password: Option<String>) -> Result<()> {
+/// Carries the state needed to obfuscate repo-side filenames when "encrypt names"
+/// mode is on: the HMAC subkey and the (mutable, to be saved once) name manifest.
+struct NameObfuscation {
+    name_key: [u8; 32],
+    manifest: NameManifest,
+}
+
+pub fn execute(
+    stubs_or_paths: Vec<String>,
+    encrypt: bool,
+    _password: Option<String>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> Result<()> {
     let repo_path = std::env::current_dir()?;
     let manager = ConfigManager::new(repo_path.clone());
 
@@ -20,36 +36,72 @@ pub fn execute(stubs_or_paths: Vec<String>, encrypt: bool, _password: Option<Str
     }
 
     let mut tracked = manager.load_tracked_files()?;
-    
+
     // Handle encryption setup if needed
     let encryption_key = if encrypt {
         Some(setup_encryption_if_needed(&repo_path)?)
     } else {
         None
     };
-    
+
+    let mut name_obfuscation = match encryption_key.as_ref() {
+        Some(key) if manager.load_config()?.encrypt_names_enabled() => {
+            let name_key = NameManifest::derive_name_key(key);
+            let manifest = NameManifest::load(&repo_path, key)?;
+            Some(NameObfuscation { name_key, manifest })
+        }
+        _ => None,
+    };
+
     // Process each stub or path
     for stub_or_path in stubs_or_paths {
         // Check if it's a direct path or a stub
         let is_direct_path = stub_or_path.contains('/') || stub_or_path.starts_with('~') || stub_or_path.starts_with('.');
-        
+
         if is_direct_path {
             // Direct file/folder path
-            if let Err(e) = add_direct_path(&repo_path, &manager, &mut tracked, &stub_or_path, encrypt, encryption_key.as_ref()) {
+            if let Err(e) = add_direct_path(&repo_path, &manager, &mut tracked, &stub_or_path, encrypt, encryption_key.as_ref(), &include, &exclude, name_obfuscation.as_mut()) {
                 print_error(&format!("Failed to add {}: {}", stub_or_path, e));
             }
         } else {
             // Stub from database
-            if let Err(e) = add_from_stub(&repo_path, &manager, &mut tracked, &stub_or_path, encrypt, encryption_key.as_ref()) {
+            if let Err(e) = add_from_stub(&repo_path, &manager, &mut tracked, &stub_or_path, encrypt, encryption_key.as_ref(), name_obfuscation.as_mut()) {
                 print_error(&format!("Failed to add {}: {}", stub_or_path, e));
             }
         }
     }
-    
+
+    if let (Some(key), Some(obfuscation)) = (encryption_key.as_ref(), name_obfuscation.as_ref()) {
+        obfuscation.manifest.save(&repo_path, key)
+            .context("Failed to save name manifest")?;
+    }
+
     manager.save_tracked_files(&tracked)?;
     Ok(())
 }
 
+/// Resolve the repo-side path for a tracked file, obfuscating the name (and recording
+/// the mapping in the manifest) when "encrypt names" mode is active.
+fn repo_path_for(repo_path: &std::path::Path, normalized_path: &str, obfuscation: Option<&mut NameObfuscation>) -> std::path::PathBuf {
+    match obfuscation {
+        Some(obfuscation) => {
+            let opaque_name = NameManifest::obfuscate(&obfuscation.name_key, normalized_path);
+            obfuscation.manifest.insert(opaque_name.clone(), normalized_path.to_string());
+            repo_path.join(opaque_name)
+        }
+        None => repo_path.join(normalized_path.trim_start_matches("~/").trim_start_matches('/')),
+    }
+}
+
+/// Best-effort label identifying this machine as a recipient, falling back to a
+/// generic name when the hostname can't be determined.
+fn hostname_label() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "primary".to_string())
+}
+
 fn setup_encryption_if_needed(repo_path: &std::path::Path) -> Result<[u8; 32]> {
     if FileEncryptor::is_encryption_setup(repo_path) {
         // Encryption already set up, load the key
@@ -74,8 +126,10 @@ fn setup_encryption_if_needed(repo_path: &std::path::Path) -> Result<[u8; 32]> {
             bail!("Encryption setup cancelled. Please save your seed phrase before continuing.");
         }
         
-        // Derive and save the key
-        let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic);
+        // Generate a random data key and wrap it for this machine, so other machines can
+        // later be added as recipients from their own seed phrase via `add_recipient`.
+        let label = hostname_label();
+        let key = FileEncryptor::setup_multi_recipient(repo_path, &label, &mnemonic)?;
         FileEncryptor::save_key_to_repo(repo_path, &key)?;
         print_success("Encryption key saved to repository");
         
@@ -85,13 +139,14 @@ fn setup_encryption_if_needed(repo_path: &std::path::Path) -> Result<[u8; 32]> {
 
 fn add_from_stub(
     repo_path: &std::path::Path,
-    _manager: &ConfigManager,
+    manager: &ConfigManager,
     tracked: &mut Vec<TrackedFile>,
     stub: &str,
     encrypt: bool,
-    encryption_key: Option<&[u8; 32]>
+    encryption_key: Option<&[u8; 32]>,
+    mut name_obfuscation: Option<&mut NameObfuscation>,
 ) -> Result<()> {
-    let db = ConfigDatabase::new(repo_path);
+    let db = ConfigDatabase::new(repo_path).with_remote_sources(manager.load_remote_stub_sources()?);
     let entry = db.load_stub(stub)?;
     
     if entry.is_none() {
@@ -113,10 +168,10 @@ fn add_from_stub(
 
     for file_path in &files_to_track {
         let (home_path, full_home_path) = resolve_file_path(file_path);
-        
+
         if full_home_path.exists() {
-            let repo_file_path = repo_path.join(file_path.trim_start_matches("~/").trim_start_matches('/'));
-            
+            let repo_file_path = repo_path_for(repo_path, &home_path, name_obfuscation.as_mut().map(|o| &mut **o));
+
             if let Some(key) = encryption_key {
                 let encrypted_path = repo_file_path.with_extension("enc");
                 FileEncryptor::encrypt_file(&full_home_path, &encrypted_path, key)
@@ -136,10 +191,12 @@ fn add_from_stub(
                 stub: Some(stub.to_string()),
                 path: home_path,
                 encrypted: encrypt,
+                include: None,
+                exclude: None,
             });
         }
     }
-    
+
     Ok(())
 }
 
@@ -149,15 +206,18 @@ fn add_direct_path(
     tracked: &mut Vec<TrackedFile>,
     path: &str,
     encrypt: bool,
-    encryption_key: Option<&[u8; 32]>
+    encryption_key: Option<&[u8; 32]>,
+    include: &[String],
+    exclude: &[String],
+    name_obfuscation: Option<&mut NameObfuscation>,
 ) -> Result<()> {
     let expanded_path = FileSyncer::expand_tilde(path);
-    
+
     if !expanded_path.exists() {
         print_error(&format!("Path does not exist: {}", path));
         bail!("Path not found");
     }
-    
+
     // Normalize path to start with ~/
     let normalized_path = if let Some(home) = dirs::home_dir() {
         if let Ok(rel) = expanded_path.strip_prefix(&home) {
@@ -168,11 +228,31 @@ fn add_direct_path(
     } else {
         path.to_string()
     };
-    
+
     print_info(&format!("Adding direct path: {}...", normalized_path));
-    
-    let repo_file_path = repo_path.join(normalized_path.trim_start_matches("~/").trim_start_matches('/'));
-    
+
+    if expanded_path.is_dir() {
+        let repo_dir = repo_path.join(normalized_path.trim_start_matches("~/").trim_start_matches('/'));
+        add_directory(&expanded_path, &repo_dir, encrypt, encryption_key, include, exclude)?;
+
+        if !tracked.iter().any(|t| t.path == normalized_path) {
+            tracked.push(TrackedFile {
+                stub: None,
+                path: normalized_path.clone(),
+                encrypted: encrypt,
+                include: (!include.is_empty()).then(|| include.to_vec()),
+                exclude: (!exclude.is_empty()).then(|| exclude.to_vec()),
+            });
+            print_success(&format!("Added directory to tracked files: {}", normalized_path));
+        } else {
+            print_info(&format!("Already tracked: {}", normalized_path));
+        }
+
+        return Ok(());
+    }
+
+    let repo_file_path = repo_path_for(repo_path, &normalized_path, name_obfuscation);
+
     if let Some(key) = encryption_key {
         let encrypted_path = repo_file_path.with_extension("enc");
         FileEncryptor::encrypt_file(&expanded_path, &encrypted_path, key)
@@ -183,18 +263,66 @@ fn add_direct_path(
             .context(format!("Failed to sync {}", normalized_path))?;
         print_success(&format!("Copied: {}", normalized_path));
     }
-    
+
     if !tracked.iter().any(|t| t.path == normalized_path) {
         tracked.push(TrackedFile {
             stub: None,
             path: normalized_path.clone(),
             encrypted: encrypt,
+            include: None,
+            exclude: None,
         });
         print_success(&format!("Added to tracked files: {}", normalized_path));
     } else {
         print_info(&format!("Already tracked: {}", normalized_path));
     }
-    
+
+    Ok(())
+}
+
+/// Copies every file under `home_dir` that matches `include`/`exclude` into `repo_dir`,
+/// preserving its relative path (and encrypting each file individually, with a literal
+/// `.enc` suffix, if `encrypt` is set). Used for `dotfiles add` on a directory.
+fn add_directory(
+    home_dir: &std::path::Path,
+    repo_dir: &std::path::Path,
+    encrypt: bool,
+    encryption_key: Option<&[u8; 32]>,
+    include: &[String],
+    exclude: &[String],
+) -> Result<()> {
+    let spec = DirSpec::compile(home_dir, include, exclude)?;
+    let mut copied = 0;
+
+    for entry in WalkDir::new(home_dir).min_depth(1) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(home_dir)?;
+        if !spec.matches(relative, false) {
+            continue;
+        }
+
+        if let Some(key) = encryption_key {
+            let dest = repo_dir.join(encrypted_member_name(relative));
+            FileEncryptor::encrypt_file(entry.path(), &dest, key)
+                .context(format!("Failed to encrypt {}", entry.path().display()))?;
+        } else {
+            let dest = repo_dir.join(relative);
+            FileSyncer::sync_file(entry.path(), &dest)
+                .context(format!("Failed to sync {}", entry.path().display()))?;
+        }
+        copied += 1;
+    }
+
+    if copied > 0 {
+        print_success(&format!("Copied {} file(s){}", copied, if encrypt { " (encrypted)" } else { "" }));
+    } else {
+        print_info("No files matched the include/exclude rules");
+    }
+
     Ok(())
 }
 
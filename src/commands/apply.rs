@@ -0,0 +1,237 @@
+use anyhow::{bail, Result};
+use crate::backup::{now_timestamp, BackupManifest};
+use crate::config::{ConfigManager, TrackedFile};
+use crate::encryption::FileEncryptor;
+use crate::sync::FileSyncer;
+use crate::utils::{print_error, print_info, print_section, print_success, print_warning};
+use colored::Colorize;
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+enum ApplyOutcome {
+    Applied,
+    BackedUpAndApplied,
+    Skipped,
+}
+
+/// Restores tracked files from the repo into `$HOME`, the inverse of `sync_local`.
+/// Used to deploy a repo's configuration onto a fresh machine: every tracked file
+/// whose home copy is missing or out of sync with the repo is overwritten, backing up
+/// any existing home file first (mirroring homesync's `apply`).
+pub fn execute(filters: Vec<String>) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    let tracked = manager.load_tracked_files()?;
+
+    if tracked.is_empty() {
+        println!("No files are tracked yet.");
+        println!("\nUse {} to start tracking files.", "dotfiles add <stub>".cyan());
+        return Ok(());
+    }
+
+    // Mirrors `add`'s stub/path dispatch and `remove`'s normalization: a filter entry
+    // that looks like a path is normalized to the `~/...` form tracked files are stored
+    // under, while anything else is matched against the tracked stub name.
+    let normalized_filters: Vec<String> = filters.iter().map(|f| normalize_filter(f)).collect();
+
+    let has_encrypted = tracked.iter().any(|f| f.encrypted);
+    let encryption_key = if has_encrypted {
+        Some(get_encryption_key_if_needed(&repo_path)?)
+    } else {
+        None
+    };
+
+    print_section("Applying Tracked Files");
+
+    let mut backup_manifest = BackupManifest::load(&repo_path);
+    let backup_timestamp = now_timestamp()?;
+
+    let mut applied = 0;
+    let mut backed_up = 0;
+    let mut skipped = 0;
+
+    for file in &tracked {
+        if !normalized_filters.is_empty() {
+            let matches_stub = file.stub.as_deref().is_some_and(|s| normalized_filters.iter().any(|f| f == s));
+            let matches_path = normalized_filters.iter().any(|f| f == &file.path);
+            if !matches_stub && !matches_path {
+                continue;
+            }
+        }
+
+        match apply_file(&repo_path, file, encryption_key.as_ref(), &mut backup_manifest, backup_timestamp)? {
+            ApplyOutcome::Applied => {
+                applied += 1;
+                print_success(&format!("Applied: {}", file.path));
+            }
+            ApplyOutcome::BackedUpAndApplied => {
+                applied += 1;
+                backed_up += 1;
+                print_success(&format!("Applied (backed up previous): {}", file.path));
+            }
+            ApplyOutcome::Skipped => {
+                skipped += 1;
+                print_info(&format!("Skipped (already in sync or not in repo): {}", file.path));
+            }
+        }
+    }
+
+    if backed_up > 0 {
+        let retention = manager.load_backup_retention()?;
+        let pruned = backup_manifest.prune(&repo_path, retention)?;
+        backup_manifest.save(&repo_path)?;
+        if pruned > 0 {
+            print_info(&format!("Pruned {} old backup snapshot(s) beyond retention of {}", pruned, retention));
+        }
+    }
+
+    println!();
+    print_success(&format!(
+        "Applied {} files ({} backed up, {} skipped)",
+        applied, backed_up, skipped
+    ));
+
+    Ok(())
+}
+
+fn apply_file(
+    repo_path: &Path,
+    file: &TrackedFile,
+    key: Option<&[u8; 32]>,
+    backup_manifest: &mut BackupManifest,
+    backup_timestamp: u64,
+) -> Result<ApplyOutcome> {
+    let home_path = FileSyncer::expand_tilde(&file.path);
+    let repo_relative = file.path.trim_start_matches("~/").trim_start_matches('/');
+    let repo_file = if file.encrypted {
+        repo_path.join(repo_relative).with_extension("enc")
+    } else {
+        repo_path.join(repo_relative)
+    };
+
+    if !repo_file.exists() {
+        return Ok(ApplyOutcome::Skipped);
+    }
+
+    if !files_match(repo_path, &home_path, &repo_file, file.encrypted, key)? {
+        let backed_up = if home_path.exists() {
+            backup_manifest.backup(repo_path, &file.path, &home_path, backup_timestamp)?;
+            print_warning(&format!("Backed up existing file to: .backup/{}/{}", backup_timestamp, repo_relative));
+            true
+        } else {
+            false
+        };
+
+        if let Some(parent) = home_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if file.encrypted {
+            let key = key.ok_or_else(|| anyhow::anyhow!("Encrypted file but no encryption key available: {}", file.path))?;
+            FileEncryptor::decrypt_file(&repo_file, &home_path, key)?;
+        } else if repo_file.is_dir() {
+            let ignore = crate::compare::DirIgnore::load(repo_path, &repo_file);
+            crate::compare::copy_dir_filtered(&repo_file, &home_path, &ignore)?;
+        } else {
+            FileSyncer::sync_file(&repo_file, &home_path)?;
+        }
+
+        if backed_up {
+            Ok(ApplyOutcome::BackedUpAndApplied)
+        } else {
+            Ok(ApplyOutcome::Applied)
+        }
+    } else {
+        Ok(ApplyOutcome::Skipped)
+    }
+}
+
+fn files_match(repo_path: &Path, home_path: &Path, repo_file: &Path, encrypted: bool, key: Option<&[u8; 32]>) -> Result<bool> {
+    if !home_path.exists() {
+        return Ok(false);
+    }
+
+    if encrypted {
+        let Some(key) = key else {
+            return Ok(false);
+        };
+        let temp_decrypted = std::env::temp_dir().join(format!("dotfiles_apply_{}", uuid::Uuid::new_v4()));
+        if FileEncryptor::decrypt_file(repo_file, &temp_decrypted, key).is_err() {
+            return Ok(false);
+        }
+        let matches = files_are_same(repo_path, home_path, &temp_decrypted);
+        let _ = std::fs::remove_file(&temp_decrypted);
+        Ok(matches)
+    } else {
+        Ok(files_are_same(repo_path, home_path, repo_file))
+    }
+}
+
+fn files_are_same(repo_path: &Path, path1: &Path, path2: &Path) -> bool {
+    use std::io::Read;
+
+    if path1.is_dir() != path2.is_dir() {
+        return false;
+    }
+
+    if path1.is_dir() {
+        let ignore = crate::compare::DirIgnore::load(repo_path, path2);
+        return crate::compare::dirs_are_same(path1, path2, &ignore);
+    }
+
+    match (std::fs::File::open(path1), std::fs::File::open(path2)) {
+        (Ok(mut f1), Ok(mut f2)) => {
+            let mut buf1 = Vec::new();
+            let mut buf2 = Vec::new();
+
+            if f1.read_to_end(&mut buf1).is_err() || f2.read_to_end(&mut buf2).is_err() {
+                return false;
+            }
+
+            buf1 == buf2
+        }
+        _ => false,
+    }
+}
+
+/// Normalizes one `stubs` filter entry: a path-like value is resolved against the
+/// home directory into the `~/...` form `TrackedFile::path` is stored as; anything else
+/// is left alone to match against `TrackedFile::stub`.
+fn normalize_filter(filter: &str) -> String {
+    let is_path = filter.contains('/') || filter.starts_with('~') || filter.starts_with('.');
+    if is_path {
+        normalize_path(filter)
+    } else {
+        filter.to_string()
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    if let Some(home) = dirs::home_dir() {
+        let expanded = FileSyncer::expand_tilde(path);
+        if let Ok(rel) = expanded.strip_prefix(&home) {
+            return format!("~/{}", rel.display());
+        }
+    }
+    path.to_string()
+}
+
+fn get_encryption_key_if_needed(repo_path: &Path) -> Result<[u8; 32]> {
+    if FileEncryptor::has_local_key() {
+        FileEncryptor::load_key_from_home()
+    } else if FileEncryptor::is_encryption_setup(repo_path) {
+        print_info("Encrypted files detected. Please enter your seed phrase.");
+        let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
+        let key = FileEncryptor::unwrap_key(repo_path, &mnemonic)?;
+        FileEncryptor::save_key_to_home(&key)?;
+        Ok(key)
+    } else {
+        bail!("No encryption key found")
+    }
+}
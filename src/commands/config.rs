@@ -1,81 +1,55 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
+use crate::cli::ConfigAction;
 use crate::config::ConfigManager;
-use crate::utils::{print_error, print_success};
-use std::path::PathBuf;
+use crate::utils::{print_error, print_section, print_success};
+use colored::Colorize;
 
-pub fn execute(key: String, value: String) -> Result<()> {
+pub fn execute(action: ConfigAction) -> Result<()> {
     let repo_path = ConfigManager::resolve_repo_path()?;
-    let manager = ConfigManager::new(repo_path.clone());
-    
+    let manager = ConfigManager::new(repo_path);
+
     if !manager.is_initialized() {
         print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
         bail!("Repository not initialized");
     }
-    
-    // Load current config (will merge local and repo configs)
-    let mut config = manager.load_config()?;
-    
-    match key.as_str() {
-        "repo_path" => {
-            let path = PathBuf::from(&value);
-            let canonical = path.canonicalize()
-                .context(format!("Failed to resolve path: {}", value))?;
-            config.repo_path = canonical.clone();
-            manager.save_local_config(canonical)?;
-            print_success(&format!("Set repo_path to: {}", value));
-        },
-        "use_xdg" => {
-            let use_xdg = value.parse::<bool>()
-                .context("Invalid boolean value. Use 'true' or 'false'")?;
-            config.use_xdg = use_xdg;
-            save_to_local_config(&manager, &config)?;
-            print_success(&format!("Set use_xdg to: {}", use_xdg));
-        },
-        "encryption_key_path" => {
-            let path = PathBuf::from(&value);
-            let canonical = path.canonicalize()
-                .context(format!("Failed to resolve path: {}", value))?;
-            config.encryption_key_path = Some(canonical);
-            save_to_local_config(&manager, &config)?;
-            print_success(&format!("Set encryption_key_path to: {}", value));
-        },
-        "tag" => {
-            config.tag = if value.is_empty() { None } else { Some(value.clone()) };
-            save_to_local_config(&manager, &config)?;
-            print_success(&format!("Set tag to: {}", value));
-        },
-        _ => {
-            print_error(&format!("Unknown config key: {}", key));
-            println!("\nAvailable keys:");
-            println!("  - repo_path");
-            println!("  - use_xdg");
-            println!("  - encryption_key_path");
-            println!("  - tag");
-            bail!("Invalid config key");
+
+    match action {
+        ConfigAction::Set { field, value } => {
+            manager.update_local_config_field(&field, &value)?;
+            print_success(&format!("Set {} to: {}", field, value));
         }
+        ConfigAction::Show => show(&manager)?,
     }
-    
+
     Ok(())
 }
 
-fn save_to_local_config(manager: &ConfigManager, config: &crate::config::DotfilesConfig) -> Result<()> {
-    let local_config_path = manager.get_local_config_path();
-    
-    // Create a minimal local config with just the settings we want to persist
-    let local_config = crate::config::DotfilesConfig {
-        use_xdg: config.use_xdg,
-        repo_path: config.repo_path.clone(),
-        home_path: config.home_path.clone(),
-        encryption_key_path: config.encryption_key_path.clone(),
-        tag: config.tag.clone(),
-        tracked_files: None,
-    };
-    
-    let content = serde_json::to_string_pretty(&local_config)
-        .context("Failed to serialize local config")?;
-    
-    std::fs::write(&local_config_path, content)
-        .context("Failed to write local config file")?;
-    
+/// Print the fully-resolved runtime config alongside which layer supplied each field,
+/// so a value that looks wrong can be traced back to the env var, project/home local
+/// config, or repo config that set it.
+fn show(manager: &ConfigManager) -> Result<()> {
+    let runtime = manager.load_runtime_config()?;
+
+    print_section("Resolved Configuration");
+
+    let rows: [(&str, String, &str); 8] = [
+        ("use_xdg", runtime.use_xdg.to_string(), runtime.provenance.use_xdg.label()),
+        ("repo_path", runtime.repo_path.display().to_string(), runtime.provenance.repo_path.label()),
+        ("home_path", runtime.home_path.display().to_string(), runtime.provenance.home_path.label()),
+        ("tag", runtime.tag.clone().unwrap_or_else(|| "(none)".to_string()), runtime.provenance.tag.label()),
+        ("git_backend", runtime.git_backend.as_str().to_string(), runtime.provenance.git_backend.label()),
+        ("git_hardening", runtime.git_hardening.to_string(), runtime.provenance.git_hardening.label()),
+        ("merge_tool", runtime.merge_tool.clone().unwrap_or_else(|| "(none)".to_string()), runtime.provenance.merge_tool.label()),
+        (
+            "encryption_backend",
+            runtime.encryption_backend.clone().unwrap_or_else(|| "(none)".to_string()),
+            runtime.provenance.encryption_backend.label(),
+        ),
+    ];
+
+    for (field, value, source) in rows {
+        println!("  {:<10} {:<30} {}", field.green().bold(), value, format!("(from {})", source).dimmed());
+    }
+
     Ok(())
 }
@@ -21,7 +21,8 @@ pub fn execute(stub: String, paths: Vec<String>, tag: Option<String>) -> Result<
 
     let config = manager.load_config()?;
     let tag_to_use = tag.or(config.tag.clone());
-    let db = ConfigDatabase::new_with_tag(&repo_path, tag_to_use.as_deref());
+    let db = ConfigDatabase::new_with_tag(&repo_path, tag_to_use.as_deref())
+        .with_remote_sources(manager.load_remote_stub_sources()?);
 
     if db.load_stub(&stub)?.is_some() {
         print_error(&format!("Stub '{}' already exists", stub));
@@ -0,0 +1,90 @@
+use anyhow::{bail, Result};
+use crate::cli::DbAction;
+use crate::config::{ConfigManager, RemoteStubSource};
+use crate::db::ConfigDatabase;
+use crate::utils::{print_error, print_info, print_section, print_success};
+
+/// Manages the remote stub catalogs configured in local config and merges them into
+/// the stub database via `ConfigDatabase::update_remote`.
+pub fn execute(action: DbAction) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    match action {
+        DbAction::AddRemote { url, branch, include, exclude } => {
+            manager.add_remote_stub_source(RemoteStubSource {
+                url: url.clone(),
+                branch,
+                included_stubs: include,
+                excluded_stubs: exclude,
+            })?;
+            print_success(&format!("Added remote stub source: {}", url));
+            print_info("Run 'dotfiles db update' to fetch it");
+        }
+        DbAction::RemoveRemote { url } => {
+            if manager.remove_remote_stub_source(&url)? {
+                print_success(&format!("Removed remote stub source: {}", url));
+            } else {
+                print_error(&format!("No remote stub source found for: {}", url));
+                bail!("Remote stub source not found");
+            }
+        }
+        DbAction::ListRemotes => list_remotes(&manager)?,
+        DbAction::Update => update(&repo_path, &manager)?,
+    }
+
+    Ok(())
+}
+
+fn list_remotes(manager: &ConfigManager) -> Result<()> {
+    let sources = manager.load_remote_stub_sources()?;
+
+    print_section("Remote Stub Catalogs");
+
+    if sources.is_empty() {
+        print_info("No remote stub sources configured. Add one with 'dotfiles db add-remote <url>'.");
+        return Ok(());
+    }
+
+    for source in sources {
+        let branch = source.branch.as_deref().unwrap_or("(default)");
+        println!("- {} [branch: {}]", source.url, branch);
+        if !source.included_stubs.is_empty() {
+            println!("    include: {}", source.included_stubs.join(", "));
+        }
+        if !source.excluded_stubs.is_empty() {
+            println!("    exclude: {}", source.excluded_stubs.join(", "));
+        }
+    }
+
+    Ok(())
+}
+
+fn update(repo_path: &std::path::Path, manager: &ConfigManager) -> Result<()> {
+    let sources = manager.load_remote_stub_sources()?;
+
+    if sources.is_empty() {
+        print_info("No remote stub sources configured. Add one with 'dotfiles db add-remote <url>'.");
+        return Ok(());
+    }
+
+    let db = ConfigDatabase::new(repo_path);
+
+    for source in &sources {
+        print_section(&format!("Updating '{}'", source.url));
+        match db.update_remote(source) {
+            Ok(stats) => print_success(&format!(
+                "'{}': {} stubs included, {} filtered out",
+                source.url, stats.included, stats.filtered_out
+            )),
+            Err(e) => print_error(&format!("Failed to update '{}': {}", source.url, e)),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::ConfigManager;
+use crate::git::GitRepo;
+use crate::utils::{print_error, print_info, print_success};
+
+/// Packages the full tracked history into a single bundle file for offline transfer to
+/// an air-gapped or new machine, with no remote required on either end.
+pub fn execute(file: PathBuf, sign: bool) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
+
+    if !git.is_repo() {
+        print_error("Not a git repository. Initialize git first.");
+        bail!("Not a git repository");
+    }
+
+    let branch = git.get_current_branch()?;
+
+    print_info(&format!("Exporting branch '{}' to {}...", branch, file.display()));
+    git.create_bundle(&file, &branch)?;
+    print_success(&format!("Exported bundle: {}", file.display()));
+
+    if sign {
+        let sig_path = sign_bundle(&file)?;
+        print_success(&format!("Wrote detached signature: {}", sig_path.display()));
+    }
+
+    Ok(())
+}
+
+/// Signs the bundle's SHA-256 digest (not the bundle bytes themselves, so recipients
+/// only need to hash the file once to both verify integrity and check the signature)
+/// with `gpg --detached-sign`, writing the signature alongside the bundle as
+/// `<file>.sig`.
+fn sign_bundle(bundle: &Path) -> Result<PathBuf> {
+    let digest = hash_file(bundle)?.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    let sig_path = bundle.with_extension(append_ext(bundle, "sig"));
+
+    let mut child = Command::new("gpg")
+        .args(["--detached-sign", "--armor", "--output"])
+        .arg(&sig_path)
+        .args(["--yes", "-"])
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to invoke gpg; is it installed and on PATH?")?;
+
+    child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(digest.as_bytes())
+        .context("Failed to write digest to gpg")?;
+
+    let status = child.wait().context("Failed to wait on gpg")?;
+    if !status.success() {
+        bail!("gpg --detached-sign failed");
+    }
+
+    Ok(sig_path)
+}
+
+fn append_ext(path: &Path, ext: &str) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(existing) => format!("{existing}.{ext}"),
+        None => ext.to_string(),
+    }
+}
+
+fn hash_file(path: &Path) -> Result<[u8; 32]> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().into())
+}
@@ -0,0 +1,106 @@
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config::ConfigManager;
+use crate::git::GitRepo;
+use crate::utils::{print_error, print_info, print_success};
+
+/// Verifies and merges a bundle produced by `dotfiles export` into the local repo,
+/// fast-forwarding via the same `GitBackend` plumbing `pull_rebase` uses for a named
+/// remote — the bundle file just stands in for the remote.
+pub fn execute(file: PathBuf, verify_signature: bool) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    if !file.exists() {
+        bail!("Bundle file does not exist: {}", file.display());
+    }
+
+    if verify_signature {
+        verify_bundle_signature(&file)?;
+        print_success("Signature verified");
+    }
+
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
+
+    if !git.is_repo() {
+        print_error("Not a git repository. Initialize git first.");
+        bail!("Not a git repository");
+    }
+
+    let branch = git.get_current_branch()?;
+
+    print_info(&format!("Importing {} into branch '{}'...", file.display(), branch));
+    git.import_bundle(&file, &branch)?;
+    print_success("Import completed successfully");
+
+    Ok(())
+}
+
+/// Recomputes the bundle's SHA-256 and checks it against the detached signature
+/// written by `dotfiles export --sign` at `<file>.sig`, the same digest-then-sign
+/// scheme `sign_bundle` uses on export.
+fn verify_bundle_signature(bundle: &Path) -> Result<()> {
+    let sig_path = bundle.with_extension(append_ext(bundle, "sig"));
+    if !sig_path.exists() {
+        bail!("No signature file found at {}", sig_path.display());
+    }
+
+    let digest = hash_file(bundle)?.iter().map(|b| format!("{b:02x}")).collect::<String>();
+
+    let mut child = Command::new("gpg")
+        .args(["--verify"])
+        .arg(&sig_path)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("Failed to invoke gpg; is it installed and on PATH?")?;
+
+    child
+        .stdin
+        .take()
+        .expect("piped stdin")
+        .write_all(digest.as_bytes())
+        .context("Failed to write digest to gpg")?;
+
+    let status = child.wait().context("Failed to wait on gpg")?;
+    if !status.success() {
+        bail!("Signature verification failed for {}", bundle.display());
+    }
+
+    Ok(())
+}
+
+fn append_ext(path: &Path, ext: &str) -> String {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(existing) => format!("{existing}.{ext}"),
+        None => ext.to_string(),
+    }
+}
+
+fn hash_file(path: &Path) -> Result<[u8; 32]> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().into())
+}
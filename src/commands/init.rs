@@ -5,7 +5,7 @@ use crate::config::{ConfigManager, DotfilesConfig};
 use crate::git::GitRepo;
 use crate::utils::{print_success, print_info};
 
-pub fn execute(path: Option<PathBuf>, tag: Option<String>) -> Result<()> {
+pub fn execute(path: Option<PathBuf>, tag: Option<String>, remote: Option<String>) -> Result<()> {
     let repo_path = path.unwrap_or_else(|| PathBuf::from("."));
     
     if !repo_path.exists() {
@@ -28,6 +28,7 @@ pub fn execute(path: Option<PathBuf>, tag: Option<String>) -> Result<()> {
     let mut config = DotfilesConfig::default();
     config.repo_path = canonical_repo_path.clone();
     config.tag = tag.clone();
+    config.remote = remote.clone();
     
     if let Some(home) = dirs::home_dir() {
         config.home_path = home;
@@ -55,6 +56,9 @@ pub fn execute(path: Option<PathBuf>, tag: Option<String>) -> Result<()> {
     fs::create_dir_all(custom_path.join("default_configs"))?;
     print_success("Created custom_db directory structure");
 
+    fs::create_dir_all(repo_path.join(crate::backup::BACKUP_DIR))
+        .context("Failed to create .backup directory")?;
+
     let git = GitRepo::new(&repo_path);
     if !git.is_repo() {
         git.init().context("Failed to initialize git repository")?;
@@ -63,6 +67,14 @@ pub fn execute(path: Option<PathBuf>, tag: Option<String>) -> Result<()> {
         print_info("Git repository already exists");
     }
 
+    if let Some(ref url) = remote {
+        if !git.has_remote()? {
+            git.add_remote("origin", url)
+                .context("Failed to add remote")?;
+            print_success(&format!("Added git remote 'origin': {}", url));
+        }
+    }
+
     // Create .gitignore
     let gitignore_path = repo_path.join(".gitignore");
     let mut gitignore_content = String::new();
@@ -84,7 +96,22 @@ pub fn execute(path: Option<PathBuf>, tag: Option<String>) -> Result<()> {
         gitignore_content.push_str("\n# Local backups (for emergency recovery)\n.backup/\n");
         updated = true;
     }
-    
+
+    // Cached decrypt-and-compare results for sync - local to this machine only
+    if !gitignore_content.contains(".dotfiles.synccache.json") {
+        gitignore_content
+            .push_str("\n# Sync change-detection cache\n.dotfiles.synccache.json\n");
+        updated = true;
+    }
+
+    // The repo-local encryption key must never be committed - only the seed phrase
+    // (held by the user) should grant access to .enc files. See 'dotfiles unlock'.
+    if !gitignore_content.contains(".dotfiles.encryption.key") {
+        gitignore_content
+            .push_str("\n# Repo-local encryption key (re-derive with 'dotfiles unlock')\n.dotfiles.encryption.key\n");
+        updated = true;
+    }
+
     if updated {
         fs::write(&gitignore_path, gitignore_content)
             .context("Failed to write .gitignore")?;
@@ -17,7 +17,7 @@ pub fn execute(all: bool, stub_filters: Vec<String>) -> Result<()> {
     }
 
     if all {
-        list_all_available(&repo_path)?;
+        list_all_available(&manager, &repo_path)?;
     } else {
         show_status(&manager, &repo_path, stub_filters)?;
     }
@@ -98,8 +98,8 @@ fn show_status(manager: &ConfigManager, repo_path: &std::path::PathBuf, stub_fil
     Ok(())
 }
 
-fn list_all_available(repo_path: &std::path::PathBuf) -> Result<()> {
-    let db = ConfigDatabase::new(repo_path);
+fn list_all_available(manager: &ConfigManager, repo_path: &std::path::PathBuf) -> Result<()> {
+    let db = ConfigDatabase::new(repo_path).with_remote_sources(manager.load_remote_stub_sources()?);
     let stubs = db.list_all_stubs()?;
 
     if stubs.is_empty() {
@@ -112,8 +112,12 @@ fn list_all_available(repo_path: &std::path::PathBuf) -> Result<()> {
     print_section("Available Stubs");
 
     for stub in stubs {
-        if let Ok(Some((name, files, is_custom))) = db.get_stub_info(&stub) {
-            let stub_type = if is_custom { "custom".magenta() } else { "default".blue() };
+        if let Ok(Some((name, files, origin))) = db.get_stub_info(&stub) {
+            let stub_type = match &origin {
+                crate::db::StubOrigin::Custom => origin.label().magenta(),
+                crate::db::StubOrigin::Remote(_) => origin.label().cyan(),
+                crate::db::StubOrigin::Embedded => origin.label().blue(),
+            };
             println!("\n{} ({}) [{}]", name.green().bold(), stub.yellow(), stub_type);
             for file in files.iter().take(3) {
                 println!("  {}", file.dimmed());
@@ -170,7 +174,7 @@ fn check_file_status(repo_path: &std::path::PathBuf, home_path: &str, encrypted:
                 }
             } else {
                 // Compare unencrypted files
-                if files_are_same(&home_full, &repo_file) {
+                if files_are_same(repo_path, &home_full, &repo_file) {
                     FileStatus::InSync
                 } else {
                     FileStatus::OutOfSync
@@ -186,7 +190,7 @@ fn get_encryption_key_if_needed(repo_path: &std::path::PathBuf) -> Result<[u8; 3
     } else if FileEncryptor::is_encryption_setup(repo_path) {
         // Ask for seed phrase
         let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
-        let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic);
+        let key = FileEncryptor::unwrap_key(repo_path, &mnemonic)?;
         FileEncryptor::save_key_to_home(&key)?;
         Ok(key)
     } else {
@@ -194,15 +198,16 @@ fn get_encryption_key_if_needed(repo_path: &std::path::PathBuf) -> Result<[u8; 3
     }
 }
 
-fn files_are_same(path1: &std::path::Path, path2: &std::path::Path) -> bool {
+fn files_are_same(repo_path: &std::path::Path, path1: &std::path::Path, path2: &std::path::Path) -> bool {
     use std::io::Read;
-    
+
     if path1.is_dir() != path2.is_dir() {
         return false;
     }
 
     if path1.is_dir() {
-        return true;
+        let ignore = crate::compare::DirIgnore::load(repo_path, path2);
+        return crate::compare::dirs_are_same(path1, path2, &ignore);
     }
 
     // Compare file contents directly
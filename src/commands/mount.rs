@@ -0,0 +1,43 @@
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+
+use crate::config::ConfigManager;
+use crate::encryption::FileEncryptor;
+use crate::mount::DotfilesFs;
+use crate::utils::{print_error, print_info};
+
+pub fn execute(mountpoint: PathBuf) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    if !mountpoint.exists() {
+        bail!("Mountpoint does not exist: {}", mountpoint.display());
+    }
+
+    let tracked = manager.load_tracked_files()?;
+    let has_encrypted = tracked.iter().any(|f| f.encrypted);
+
+    let key = if has_encrypted {
+        FileEncryptor::load_key_from_home()
+            .context("Encrypted files are tracked but no encryption key was found; run 'dotfiles unlock' first")?
+    } else {
+        [0u8; 32]
+    };
+
+    let fs = DotfilesFs::new(&tracked, &repo_path, key);
+
+    print_info(&format!("Mounting dotfiles at {} (read-only; Ctrl+C to unmount)", mountpoint.display()));
+    fuser::mount2(
+        fs,
+        &mountpoint,
+        &[fuser::MountOption::RO, fuser::MountOption::FSName("dotfiles".to_string())],
+    )
+    .context("Failed to mount dotfiles filesystem")?;
+
+    Ok(())
+}
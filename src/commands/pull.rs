@@ -1,4 +1,5 @@
 use anyhow::{bail, Result};
+use crate::commands::sync::render_progress;
 use crate::config::ConfigManager;
 use crate::git::GitRepo;
 use crate::utils::{print_error, print_info, print_success, print_warning};
@@ -12,7 +13,8 @@ pub fn execute() -> Result<()> {
         bail!("Repository not initialized");
     }
 
-    let git = GitRepo::new(&repo_path);
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
 
     if !git.is_repo() {
         print_error("Not a git repository. Initialize git first.");
@@ -26,15 +28,23 @@ pub fn execute() -> Result<()> {
     }
 
     if !git.has_remote()? {
-        print_warning("No remote repository configured");
-        print_info("Add a remote with: git remote add origin <url>");
-        return Ok(());
+        match manager.load_remote()? {
+            Some(url) => {
+                print_info(&format!("No git remote configured; adding 'origin' from config: {}", url));
+                git.add_remote("origin", &url)?;
+            }
+            None => {
+                print_warning("No remote repository configured");
+                print_info("Add a remote with: dotfiles remote set <url>");
+                return Ok(());
+            }
+        }
     }
 
     print_info("Pulling from remote repository...");
 
     let branch = git.get_current_branch()?;
-    git.pull("origin", &branch)?;
+    git.pull_rebase_with_progress("origin", &branch, &mut render_progress)?;
 
     print_success("Pull completed successfully");
 
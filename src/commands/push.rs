@@ -1,5 +1,6 @@
 use anyhow::{bail, Result};
 use colored::Colorize;
+use crate::commands::sync::render_progress;
 use crate::config::ConfigManager;
 use crate::git::GitRepo;
 use crate::utils::{print_error, print_info, print_success, print_warning};
@@ -13,7 +14,8 @@ pub fn execute() -> Result<()> {
         bail!("Repository not initialized");
     }
 
-    let git = GitRepo::new(&repo_path);
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
 
     if !git.is_repo() {
         print_error("Not a git repository. Initialize git first.");
@@ -31,15 +33,23 @@ pub fn execute() -> Result<()> {
     }
 
     if !git.has_remote()? {
-        print_warning("No remote repository configured");
-        print_info("Add a remote with: git remote add origin <url>");
-        return Ok(());
+        match manager.load_remote()? {
+            Some(url) => {
+                print_info(&format!("No git remote configured; adding 'origin' from config: {}", url));
+                git.add_remote("origin", &url)?;
+            }
+            None => {
+                print_warning("No remote repository configured");
+                print_info("Add a remote with: dotfiles remote set <url>");
+                return Ok(());
+            }
+        }
     }
 
     print_info("Pushing to remote repository...");
 
     let branch = git.get_current_branch()?;
-    git.push("origin", &branch)?;
+    git.push_with_progress("origin", &branch, &mut render_progress)?;
 
     print_success("Push completed successfully");
 
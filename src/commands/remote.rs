@@ -0,0 +1,44 @@
+use anyhow::{bail, Result};
+use crate::cli::RemoteAction;
+use crate::config::ConfigManager;
+use crate::git::GitRepo;
+use crate::utils::{print_error, print_info, print_success};
+
+pub fn execute(action: RemoteAction) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    match action {
+        RemoteAction::Set { url } => {
+            let mut config = manager.load_config()?;
+            config.remote = Some(url.clone());
+            manager.save_config(&config)?;
+            print_success(&format!("Saved remote to config: {}", url));
+
+            let git = GitRepo::new(&repo_path);
+            if !git.has_remote()? {
+                git.add_remote("origin", &url)?;
+                print_success("Added git remote 'origin'");
+            } else {
+                print_info("Git remote already configured; config updated only");
+            }
+        }
+        RemoteAction::Get => match manager.load_remote()? {
+            Some(url) => println!("{}", url),
+            None => print_info("No remote configured"),
+        },
+        RemoteAction::Unset => {
+            let mut config = manager.load_config()?;
+            config.remote = None;
+            manager.save_config(&config)?;
+            print_success("Cleared configured remote");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anyhow::{bail, Context, Result};
+use crate::backup::BackupManifest;
+use crate::compare::{copy_dir_filtered, DirIgnore};
+use crate::config::ConfigManager;
+use crate::sync::FileSyncer;
+use crate::utils::{print_error, print_info, print_section, print_success, print_warning};
+use std::fs;
+
+/// Restores a `.backup/` snapshot batch taken before a destructive `apply`/`restore`,
+/// the counterpart to `BackupManifest::backup`. With `at` unset, restores the most
+/// recent snapshot; otherwise the most recent snapshot at or before that timestamp.
+pub fn execute(at: Option<u64>) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    let manifest = BackupManifest::load(&repo_path);
+    if manifest.is_empty() {
+        print_info("No backups recorded yet. Nothing to roll back.");
+        return Ok(());
+    }
+
+    let timestamp = match manifest.resolve_timestamp(at) {
+        Some(timestamp) => timestamp,
+        None => match at {
+            Some(at) => bail!("No snapshot at or before timestamp {at}"),
+            None => bail!("No snapshot found"),
+        },
+    };
+
+    let entries = manifest.entries_at(timestamp);
+    print_section(&format!("Rolling Back to Snapshot {timestamp}"));
+
+    let mut restored = 0;
+    let mut skipped = 0;
+
+    for entry in entries {
+        let backup_abs = repo_path.join(&entry.backup_path);
+        if !backup_abs.exists() {
+            print_warning(&format!("Snapshot missing on disk, skipping: {}", entry.original_path));
+            skipped += 1;
+            continue;
+        }
+
+        let home_path = FileSyncer::expand_tilde(&entry.original_path);
+        if let Some(parent) = home_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if backup_abs.is_dir() {
+            copy_dir_filtered(&backup_abs, &home_path, &DirIgnore::none(&backup_abs))
+                .with_context(|| format!("Failed to restore {}", entry.original_path))?;
+        } else {
+            fs::copy(&backup_abs, &home_path).with_context(|| format!("Failed to restore {}", entry.original_path))?;
+        }
+
+        print_success(&format!("Restored: {}", entry.original_path));
+        restored += 1;
+    }
+
+    println!();
+    print_success(&format!(
+        "Rolled back snapshot {} ({} restored, {} skipped)",
+        timestamp, restored, skipped
+    ));
+
+    Ok(())
+}
@@ -1,12 +1,20 @@
 use anyhow::Result;
 use colored::Colorize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use crate::config::ConfigManager;
 use crate::db::ConfigDatabase;
+use crate::git_status::{GitFileState, RepoStatus};
+use crate::hash_manifest::HashManifest;
 use crate::sync::FileSyncer;
 use crate::utils::{print_section, print_info};
 
-pub fn execute() -> Result<()> {
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+pub fn execute(watch: bool) -> Result<()> {
     let repo_path = ConfigManager::resolve_repo_path()?;
     let manager = ConfigManager::new(repo_path.clone());
 
@@ -18,147 +26,361 @@ pub fn execute() -> Result<()> {
     print_section("Scanning System for Dotfiles");
     println!();
 
-    let db = ConfigDatabase::new(&repo_path);
+    let db = ConfigDatabase::new(&repo_path).with_remote_sources(manager.load_remote_stub_sources()?);
+    let stubs = scannable_stubs(&repo_path, &manager, &db)?;
+
+    print_full_report(&stubs);
+
+    if watch {
+        run_watch(&repo_path, &manager, stubs)?;
+    }
+
+    Ok(())
+}
+
+/// Every stub that has at least one file present on the system, paired with its
+/// currently computed state. Shared by the initial full scan and `--watch`'s recheck
+/// so both agree on what "in sync" means.
+fn scannable_stubs(
+    repo_path: &Path,
+    manager: &ConfigManager,
+    db: &ConfigDatabase,
+) -> Result<HashMap<String, (Vec<String>, StubSyncState)>> {
     let tracked = manager.load_tracked_files()?;
-    
-    // Build a map of stub -> tracked status
-    let mut tracked_stubs: HashMap<String, bool> = HashMap::new();
+    let mut tracked_stubs: HashSet<String> = HashSet::new();
     for file in &tracked {
         if let Some(ref stub) = file.stub {
-            tracked_stubs.insert(stub.clone(), true);
+            tracked_stubs.insert(stub.clone());
         }
     }
 
-    // Get all available stubs from database
     let default_stubs = db.get_default_stubs()?;
     let custom_stubs = db.get_custom_stubs()?;
-    
+
     let mut all_stubs: Vec<(String, Vec<String>)> = Vec::new();
-    
     for (stub_name, entry) in default_stubs {
         all_stubs.push((stub_name, entry.config_files));
     }
-    
     for (stub_name, entry) in custom_stubs {
         all_stubs.push((stub_name, entry.config_files));
     }
-    
-    all_stubs.sort_by(|a, b| a.0.cmp(&b.0));
 
-    // Categorize stubs
-    let mut synced_stubs = Vec::new();
-    let mut out_of_sync_stubs = Vec::new();
-    let mut unmanaged_stubs = Vec::new();
+    // Best-effort: a repo that hasn't had `git init` run yet (or isn't a git repo for
+    // some other reason) falls back to pure on-disk comparison rather than failing the
+    // whole scan.
+    let repo_status = RepoStatus::open(repo_path).ok();
+    let mut manifest = HashManifest::load(repo_path);
 
+    let mut result = HashMap::new();
     for (stub_name, files) in all_stubs {
-        // Check if any files from this stub exist on the system
-        let mut files_exist = false;
-        for file_path in &files {
+        if !files.iter().any(|f| FileSyncer::expand_tilde(f).exists()) {
+            continue;
+        }
+
+        let state = compute_stub_state(repo_path, repo_status.as_ref(), &mut manifest, tracked_stubs.contains(&stub_name), &files);
+        result.insert(stub_name, (files, state));
+    }
+
+    manifest.save(repo_path)?;
+
+    Ok(result)
+}
+
+fn print_full_report(stubs: &HashMap<String, (Vec<String>, StubSyncState)>) {
+    let mut by_state: HashMap<StubSyncState, Vec<(&String, &Vec<String>)>> = HashMap::new();
+    for (stub_name, (files, state)) in stubs {
+        by_state.entry(*state).or_default().push((stub_name, files));
+    }
+
+    for state in StubSyncState::ALL {
+        if let Some(entries) = by_state.get(&state) {
+            print_results(state, entries);
+        }
+    }
+
+    println!();
+    println!("{}", "Summary:".bold());
+    for state in StubSyncState::ALL {
+        let count = by_state.get(&state).map(|v| v.len()).unwrap_or(0);
+        let count_str = match state.color() {
+            "green" => count.to_string().green(),
+            "yellow" => count.to_string().yellow(),
+            "cyan" => count.to_string().cyan(),
+            _ => count.to_string().white(),
+        };
+        println!("  {} {}", count_str, state.label());
+    }
+
+    if by_state.get(&StubSyncState::Unmanaged).is_some() {
+        println!();
+        println!("Tip: Add unmanaged stubs with: {}", "dotfiles add <stub>".cyan());
+    }
+}
+
+/// Registers a filesystem watcher on every scannable stub's home paths and on the repo
+/// tree, then re-evaluates only the stub(s) touched by each (debounced) batch of
+/// events and reprints just the lines whose state changed — a live version of the
+/// one-shot report above.
+fn run_watch(
+    repo_path: &Path,
+    manager: &ConfigManager,
+    mut stubs: HashMap<String, (Vec<String>, StubSyncState)>,
+) -> Result<()> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })?;
+
+    watcher.watch(repo_path, RecursiveMode::Recursive)?;
+    for (files, _) in stubs.values() {
+        for file_path in files {
             let home_path = FileSyncer::expand_tilde(file_path);
             if home_path.exists() {
-                files_exist = true;
-                break;
+                let mode = if home_path.is_dir() { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+                let _ = watcher.watch(&home_path, mode);
             }
         }
+    }
 
-        if !files_exist {
-            continue; // Skip stubs with no files on system
-        }
+    println!();
+    print_info("Watching for changes (Ctrl+C to stop)...");
+
+    let mut pending: HashSet<PathBuf> = HashSet::new();
+    let mut last_event = Instant::now();
 
-        // Determine status
-        let is_tracked = tracked_stubs.contains_key(&stub_name);
-        
-        if is_tracked {
-            // Check if files are in sync
-            let in_sync = check_stub_sync(&repo_path, &files)?;
-            if in_sync {
-                synced_stubs.push((stub_name, files));
-            } else {
-                out_of_sync_stubs.push((stub_name, files));
+    loop {
+        match rx.recv_timeout(WATCH_DEBOUNCE) {
+            Ok(Ok(event)) => {
+                pending.extend(event.paths);
+                last_event = Instant::now();
+            }
+            Ok(Err(_)) => {}
+            Err(RecvTimeoutError::Timeout) => {
+                if !pending.is_empty() && last_event.elapsed() >= WATCH_DEBOUNCE {
+                    let changed: Vec<PathBuf> = pending.drain().collect();
+                    recheck_affected_stubs(repo_path, manager, &mut stubs, &changed)?;
+                }
             }
-        } else {
-            unmanaged_stubs.push((stub_name, files));
+            Err(RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("Filesystem watcher channel disconnected");
+            }
+        }
+    }
+}
+
+fn recheck_affected_stubs(
+    repo_path: &Path,
+    manager: &ConfigManager,
+    stubs: &mut HashMap<String, (Vec<String>, StubSyncState)>,
+    changed: &[PathBuf],
+) -> Result<()> {
+    let tracked = manager.load_tracked_files()?;
+    let mut tracked_stubs: HashSet<String> = HashSet::new();
+    for file in &tracked {
+        if let Some(ref stub) = file.stub {
+            tracked_stubs.insert(stub.clone());
         }
     }
 
-    // Print results
-    print_results("✓ Synced", &synced_stubs, "green");
-    print_results("⚠ Out of Sync", &out_of_sync_stubs, "yellow");
-    print_results("○ Unmanaged", &unmanaged_stubs, "cyan");
+    let repo_status = RepoStatus::open(repo_path).ok();
+    let mut manifest = HashManifest::load(repo_path);
 
-    // Summary
-    println!();
-    println!("{}", "Summary:".bold());
-    println!("  {} synced", synced_stubs.len().to_string().green());
-    println!("  {} out of sync", out_of_sync_stubs.len().to_string().yellow());
-    println!("  {} unmanaged", unmanaged_stubs.len().to_string().cyan());
-    
-    if !unmanaged_stubs.is_empty() {
-        println!();
-        println!("Tip: Add unmanaged stubs with: {}", "dotfiles add <stub>".cyan());
+    let affected: Vec<String> = stubs
+        .iter()
+        .filter(|(_, (files, _))| stub_touches_any(repo_path, files, changed))
+        .map(|(stub_name, _)| stub_name.clone())
+        .collect();
+
+    if affected.is_empty() {
+        return Ok(());
     }
 
+    for stub_name in &affected {
+        let Some((files, old_state)) = stubs.get(stub_name).cloned() else {
+            continue;
+        };
+
+        let new_state = compute_stub_state(repo_path, repo_status.as_ref(), &mut manifest, tracked_stubs.contains(stub_name), &files);
+
+        if new_state != old_state {
+            stubs.insert(stub_name.clone(), (files, new_state));
+            println!(
+                "  {} {} {}",
+                new_state.icon(),
+                stub_name.green().bold(),
+                format!("({})", new_state.label()).dimmed()
+            );
+        }
+    }
+
+    manifest.save(repo_path)?;
+
     Ok(())
 }
 
-fn check_stub_sync(repo_path: &std::path::Path, files: &[String]) -> Result<bool> {
-    use std::fs;
-    
+fn stub_touches_any(repo_path: &Path, files: &[String], changed: &[PathBuf]) -> bool {
+    for file_path in files {
+        let home_path = FileSyncer::expand_tilde(file_path);
+        let repo_relative = file_path.trim_start_matches("~/").trim_start_matches('/');
+        let repo_file = repo_path.join(repo_relative);
+
+        for changed_path in changed {
+            if changed_path.starts_with(&home_path) || changed_path.starts_with(&repo_file) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StubSyncState {
+    /// Home matches the repo copy, the repo copy matches HEAD, and the branch is even
+    /// with its upstream.
+    Synced,
+    /// The home file differs from the repo copy, or the repo copy is missing entirely.
+    OutOfSync,
+    /// Home matches the repo copy, but the repo copy itself has staged/unstaged edits,
+    /// is untracked, or the repo has no commits yet.
+    NeedsCommit,
+    /// Home matches the committed repo copy, but the branch is ahead/behind upstream.
+    NeedsPullPush,
+    /// At least one of the stub's files is matched by `.gitignore`, so git will never
+    /// see changes to it — surfaced separately rather than folded into "out of sync".
+    Ignored,
+    /// The stub isn't tracked at all.
+    Unmanaged,
+}
+
+impl StubSyncState {
+    const ALL: [StubSyncState; 6] = [
+        StubSyncState::Synced,
+        StubSyncState::OutOfSync,
+        StubSyncState::NeedsCommit,
+        StubSyncState::NeedsPullPush,
+        StubSyncState::Ignored,
+        StubSyncState::Unmanaged,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            StubSyncState::Synced => "synced",
+            StubSyncState::OutOfSync => "out of sync",
+            StubSyncState::NeedsCommit => "needs commit",
+            StubSyncState::NeedsPullPush => "needs pull/push",
+            StubSyncState::Ignored => "gitignored",
+            StubSyncState::Unmanaged => "unmanaged",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            StubSyncState::Synced => "✓ Synced",
+            StubSyncState::OutOfSync => "⚠ Out of Sync",
+            StubSyncState::NeedsCommit => "● Needs Commit",
+            StubSyncState::NeedsPullPush => "↕ Needs Pull/Push",
+            StubSyncState::Ignored => "⊘ Gitignored",
+            StubSyncState::Unmanaged => "○ Unmanaged",
+        }
+    }
+
+    fn color(self) -> &'static str {
+        match self {
+            StubSyncState::Synced => "green",
+            StubSyncState::OutOfSync | StubSyncState::NeedsCommit => "yellow",
+            StubSyncState::NeedsPullPush | StubSyncState::Unmanaged => "cyan",
+            StubSyncState::Ignored => "white",
+        }
+    }
+
+    fn icon(self) -> colored::ColoredString {
+        match self.color() {
+            "green" => "✓".green(),
+            "yellow" => "✗".yellow(),
+            "cyan" => "○".cyan(),
+            _ => "?".white(),
+        }
+    }
+}
+
+fn compute_stub_state(
+    repo_path: &Path,
+    repo_status: Option<&RepoStatus>,
+    manifest: &mut HashManifest,
+    is_tracked: bool,
+    files: &[String],
+) -> StubSyncState {
+    if !is_tracked {
+        return StubSyncState::Unmanaged;
+    }
+
+    let mut needs_commit = false;
+    let mut ignored = false;
+
     for file_path in files {
         let home_path = FileSyncer::expand_tilde(file_path);
-        
+
         if !home_path.exists() {
             continue;
         }
-        
-        let repo_file = repo_path.join(file_path.trim_start_matches("~/").trim_start_matches('/'));
-        
-        // Check if repo file exists
+
+        let repo_relative = file_path.trim_start_matches("~/").trim_start_matches('/');
+        let repo_file = repo_path.join(repo_relative);
+
         if !repo_file.exists() {
-                    return Ok(false); // File in home but not in repo
+            return StubSyncState::OutOfSync; // File in home but not in repo
         }
-        
-        // Compare file contents
+
         if home_path.is_file() && repo_file.is_file() {
-            let home_contents = fs::read(&home_path).ok();
-            let repo_contents = fs::read(&repo_file).ok();
-            
-            if home_contents != repo_contents {
-                return Ok(false);
+            let home_key = format!("home:{file_path}");
+            let repo_key = format!("repo:{repo_relative}");
+            let home_digest = manifest.digest_for(&home_key, &home_path);
+            let repo_digest = manifest.digest_for(&repo_key, &repo_file);
+
+            if home_digest != repo_digest {
+                return StubSyncState::OutOfSync;
+            }
+        }
+
+        if let Some(status) = repo_status {
+            match status.file_state(repo_relative) {
+                GitFileState::Ignored => ignored = true,
+                GitFileState::Modified | GitFileState::Untracked => needs_commit = true,
+                GitFileState::Committed => {}
             }
         }
     }
-    
-    Ok(true)
+
+    let Some(status) = repo_status else {
+        return StubSyncState::Synced;
+    };
+
+    if ignored {
+        StubSyncState::Ignored
+    } else if needs_commit || !status.has_commits {
+        StubSyncState::NeedsCommit
+    } else if matches!(status.ahead_behind, Some((ahead, behind)) if ahead > 0 || behind > 0) {
+        StubSyncState::NeedsPullPush
+    } else {
+        StubSyncState::Synced
+    }
 }
 
-fn print_results(title: &str, stubs: &[(String, Vec<String>)], color: &str) {
+fn print_results(state: StubSyncState, stubs: &[(&String, &Vec<String>)]) {
     if stubs.is_empty() {
         return;
     }
 
     println!();
-    println!("{}", title.bold());
-    
+    println!("{}", state.title().bold());
+
     for (stub_name, files) in stubs {
         println!("\n{}", stub_name.green().bold());
-        
-        for file_path in files {
+
+        for file_path in *files {
             let home_path = FileSyncer::expand_tilde(file_path);
             if home_path.exists() {
-                let status_icon = match color {
-                    "green" => "✓".green(),
-                    "yellow" => "✗".yellow(),
-                    "cyan" => "○".cyan(),
-                    _ => "?".white(),
-                };
-                let status_text = match color {
-                    "green" => "in sync",
-                    "yellow" => "out of sync",
-                    "cyan" => "unmanaged",
-                    _ => "unknown",
-                };
-                println!("  {} {} {}", status_icon, file_path, format!("({})", status_text).dimmed());
+                println!("  {} {} {}", state.icon(), file_path, format!("({})", state.label()).dimmed());
             }
         }
     }
@@ -79,7 +79,7 @@ fn check_file_status(repo_path: &std::path::PathBuf, home_path: &str) -> FileSta
         (false, true) => FileStatus::MissingInHome,
         (true, false) => FileStatus::MissingInRepo,
         (true, true) => {
-            if files_are_same(&home_full, &repo_file) {
+            if files_are_same(repo_path, &home_full, &repo_file) {
                 FileStatus::InSync
             } else {
                 FileStatus::OutOfSync
@@ -88,13 +88,14 @@ fn check_file_status(repo_path: &std::path::PathBuf, home_path: &str) -> FileSta
     }
 }
 
-fn files_are_same(path1: &std::path::Path, path2: &std::path::Path) -> bool {
+fn files_are_same(repo_path: &std::path::Path, path1: &std::path::Path, path2: &std::path::Path) -> bool {
     if path1.is_dir() != path2.is_dir() {
         return false;
     }
 
     if path1.is_dir() {
-        return true;
+        let ignore = crate::compare::DirIgnore::load(repo_path, path2);
+        return crate::compare::dirs_are_same(path1, path2, &ignore);
     }
 
     match (std::fs::metadata(path1), std::fs::metadata(path2)) {
@@ -1,64 +1,150 @@
-use anyhow::{bail, Context, Result};
-use colored::Colorize;
 use crate::config::{ConfigManager, TrackedFile};
+use crate::dir_spec::{encrypted_member_name, strip_encrypted_member_suffix, DirSpec};
 use crate::encryption::FileEncryptor;
-use crate::git::GitRepo;
+use crate::git::{GitRepo, PushProgress};
+use crate::merge::diff3_merge;
 use crate::sync::FileSyncer;
+use crate::sync_cache::SyncCache;
 use crate::utils::{print_error, print_info, print_success, print_warning};
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 const TEMP_CONFLICTS_DIR: &str = ".dotfiles_conflicts_temp";
 
-pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _password: Option<String>) -> Result<()> {
+/// Renders `PushProgress` events as a single live-updating percentage line, e.g.
+/// `Writing objects: 57% (13/23), 1.2 MiB`. Used for Steps 3 and 6 so a large initial
+/// push doesn't look frozen while it transfers; also reused by the
+/// standalone `pull`/`push` commands.
+pub(crate) fn render_progress(event: PushProgress) {
+    match event {
+        PushProgress::Transfer {
+            objects,
+            total_objects,
+        } => {
+            print_progress_line("Receiving objects", objects, total_objects, None);
+        }
+        PushProgress::PushTransfer {
+            current,
+            total,
+            bytes,
+        } => {
+            print_progress_line("Writing objects", current, total, Some(bytes));
+        }
+        PushProgress::UpdateTips { refname, .. } => {
+            // A ref update means the transfer finished; clear the progress line before
+            // the caller's next print_success/print_info.
+            print!("\r{}\r", " ".repeat(60));
+            let _ = std::io::stdout().flush();
+            print_info(&format!("Updated {refname}"));
+        }
+    }
+}
+
+fn print_progress_line(label: &str, current: usize, total: usize, bytes: Option<usize>) {
+    let percent = if total == 0 {
+        100
+    } else {
+        (current * 100) / total
+    };
+    let size = match bytes {
+        Some(b) if b > 0 => format!(", {:.1} MiB", b as f64 / (1024.0 * 1024.0)),
+        _ => String::new(),
+    };
+    print!("\r{}: {}% ({}/{}){}", label, percent, current, total, size);
+    let _ = std::io::stdout().flush();
+    if current >= total {
+        println!();
+    }
+}
+
+pub fn execute(
+    dir: Option<PathBuf>,
+    encryption_key_path: Option<PathBuf>,
+    _password: Option<String>,
+) -> Result<()> {
+    execute_with_options(dir, encryption_key_path, _password, false, false)
+}
+
+/// `dry_run` previews every step (import, commit, pull, export, push) without writing
+/// to the filesystem, the git index, or the remote. `checksum` swaps the plain
+/// byte-buffer comparison in `files_are_identical` for a SHA-256 digest per file,
+/// printed so divergence between home, repo, and (for encrypted files) the decrypted
+/// plaintext can be audited before committing to a real sync.
+pub fn execute_with_options(
+    dir: Option<PathBuf>,
+    encryption_key_path: Option<PathBuf>,
+    _password: Option<String>,
+    dry_run: bool,
+    checksum: bool,
+) -> Result<()> {
     // Handle --dir argument to change and save repo directory
     let repo_path = if let Some(dir_path) = dir {
-        let canonical_path = dir_path.canonicalize()
+        let canonical_path = dir_path
+            .canonicalize()
             .context("Failed to resolve directory path")?;
-        
+
         // Save to local config
         let temp_manager = ConfigManager::new(canonical_path.clone());
         temp_manager.save_local_config(canonical_path.clone())?;
-        print_success(&format!("Saved dotfiles directory to local config: {}", canonical_path.display()));
-        
+        print_success(&format!(
+            "Saved dotfiles directory to local config: {}",
+            canonical_path.display()
+        ));
+
         canonical_path
     } else {
         let repo_path = ConfigManager::resolve_repo_path()?;
         let manager = ConfigManager::new(repo_path.clone());
-        
+
         // Check if local config exists
         let local_config_path = manager.get_local_config_path();
-        
+
         // If local config doesn't exist, save it automatically
         // This allows running 'dotfiles sync' from the repo to enable global usage
         if !local_config_path.exists() {
             manager.save_local_config(repo_path.clone())?;
-            print_success(&format!("Saved dotfiles directory to local config: {}", repo_path.display()));
+            print_success(&format!(
+                "Saved dotfiles directory to local config: {}",
+                repo_path.display()
+            ));
         }
-        
+
         repo_path
     };
-    
+
     // Handle --encryption-key-path argument
     if let Some(key_path) = encryption_key_path {
-        let canonical_key_path = key_path.canonicalize()
+        let canonical_key_path = key_path
+            .canonicalize()
             .context("Failed to resolve encryption key path")?;
-        
+
         let temp_manager = ConfigManager::new(repo_path.clone());
         let mut config = temp_manager.load_config()?;
         config.encryption_key_path = Some(canonical_key_path.clone());
-        
+
         // Save to local config
         let local_config_path = temp_manager.get_local_config_path();
-        let content = serde_json::to_string_pretty(&config)
-            .context("Failed to serialize local config")?;
-        std::fs::write(&local_config_path, content)
-            .context("Failed to write local config file")?;
-        
-        print_success(&format!("Saved encryption key path to local config: {}", canonical_key_path.display()));
+        let content =
+            serde_json::to_string_pretty(&config).context("Failed to serialize local config")?;
+        std::fs::write(&local_config_path, content).context("Failed to write local config file")?;
+
+        print_success(&format!(
+            "Saved encryption key path to local config: {}",
+            canonical_key_path.display()
+        ));
     }
-    
+
     let manager = ConfigManager::new(repo_path.clone());
-    let git = GitRepo::new(&repo_path);
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(
+        &repo_path,
+        runtime_config.git_backend,
+        runtime_config.git_hardening,
+    );
 
     // --- PRE-FLIGHT CHECKS ---
     if !manager.is_initialized() {
@@ -70,20 +156,23 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
         print_error("Not a git repository. Initialize git first.");
         bail!("Not a git repository");
     }
-    
+
     // Check if we're in a rebase state
     if git.is_in_rebase()? {
         print_error("Repository is in a rebase state.");
-        println!("\nUse {} to continue after resolving conflicts.", "dotfiles sync --continue".cyan().bold());
+        println!(
+            "\nUse {} to continue after resolving conflicts.",
+            "dotfiles sync --continue".cyan().bold()
+        );
         bail!("In rebase state");
     }
-    
+
     // Clean up any temporary conflict files from previous runs
     cleanup_temp_dir(&repo_path)?;
 
     // --- SETUP ---
     let tracked = manager.load_tracked_files()?.clone();
-    
+
     if tracked.is_empty() {
         print_info("No files to track. Use 'dotfiles add' to add files.");
         return Ok(());
@@ -97,6 +186,12 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
         None
     };
 
+    // Caches decrypt-and-compare outcomes for encrypted files so a sync where nothing
+    // changed doesn't pay for a decrypt of every tracked `.enc` file.
+    let mut sync_cache = encryption_key
+        .as_ref()
+        .map(|key| SyncCache::load(&repo_path, key));
+
     // Check for remote and warn if local-only
     let has_remote = git.has_remote()?;
     if !has_remote {
@@ -105,19 +200,35 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
         println!();
     }
 
+    if dry_run {
+        println!(
+            "\n{}",
+            "DRY RUN: no files, git index, or remote will be touched."
+                .yellow()
+                .bold()
+        );
+    }
     print_info("Starting robust bidirectional sync...");
     println!();
 
     // --- STEP 1: IMPORT (Home -> Repo) ---
     print_info("Step 1/5: Importing local changes...");
-    sync_home_to_repo(&manager, &tracked, encryption_key.as_ref())?;
+    sync_home_to_repo(
+        &manager,
+        &tracked,
+        encryption_key.as_ref(),
+        sync_cache.as_mut(),
+        dry_run,
+        checksum,
+    )?;
 
     // --- STEP 2: STAGE & COMMIT ---
-    // Check if the import actually changed anything in the repo structure
-    if git.is_dirty()? {
+    if dry_run {
+        print_info("Step 2/5: (dry run) Would stage and commit local changes if any.");
+    } else if git.is_dirty()? {
         print_info("Step 2/5: Committing local changes...");
         git.add_all()?;
-        
+
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
         git.commit(&format!("dotfiles sync: {}", timestamp))?;
         print_success("Local changes committed");
@@ -133,54 +244,73 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
     } else {
         false
     };
-    
-    if has_remote && !remote_is_empty {
+
+    if dry_run {
+        if has_remote && !remote_is_empty {
+            print_info("Step 3/6: (dry run) Would pull --rebase from remote.");
+        } else {
+            print_info("Step 3/6: (dry run) No remote pull needed.");
+        }
+    } else if has_remote && !remote_is_empty {
         print_info("Step 3/6: Pulling updates from remote...");
-        
+
         // We use fetch + rebase for a cleaner history and safety.
         // If rebase fails, it returns error, and we DO NOT proceed to Step 4.
-        match git.pull_rebase("origin", &branch) {
+        match git.pull_rebase_with_progress("origin", &branch, &mut render_progress) {
             Ok(_) => print_success("Remote updates applied"),
             Err(e) => {
                 print_error("Merge conflict during update!");
-                println!("\n{}", "SAFETY LOCK ENGAGED: Home directory was NOT updated.".yellow().bold());
-                
+                println!(
+                    "\n{}",
+                    "SAFETY LOCK ENGAGED: Home directory was NOT updated."
+                        .yellow()
+                        .bold()
+                );
+
                 // Get conflicted files and decrypt if needed
                 if let Ok(conflicted_files) = git.get_conflicted_files() {
                     println!("\n{}", "Conflicted files:".yellow().bold());
-                    
+
                     let encryption_key = if tracked.iter().any(|f| f.encrypted) {
                         resolve_encryption_key(&repo_path).ok()
                     } else {
                         None
                     };
-                    
+
                     for file in &conflicted_files {
                         println!("  {} {}", "✗".red(), file);
-                        
+
                         // If it's an encrypted file, decrypt to temp for easier conflict resolution
                         if file.ends_with(".enc") {
                             if let Some(key) = encryption_key.as_ref() {
                                 let full_path = repo_path.join(file);
-                                if let Err(decrypt_err) = decrypt_to_temp(&repo_path, &full_path, key) {
-                                    print_warning(&format!("Could not decrypt {}: {}", file, decrypt_err));
+                                if let Err(decrypt_err) =
+                                    decrypt_to_temp(&repo_path, &full_path, key)
+                                {
+                                    print_warning(&format!(
+                                        "Could not decrypt {}: {}",
+                                        file, decrypt_err
+                                    ));
                                 }
                             }
                         }
                     }
-                    
+
                     if encryption_key.is_some() {
                         println!("\n{}", "Encrypted files have been decrypted to:".yellow());
                         println!("  {}", repo_path.join(TEMP_CONFLICTS_DIR).display());
                     }
                 }
-                
+
                 println!("\n{}", "To resolve:".yellow().bold());
                 println!("  1. Resolve conflicts in the files listed above");
-                println!("  2. Run {} to continue", "dotfiles sync --continue".cyan().bold());
-                
+                println!(
+                    "  2. Run {} to continue",
+                    "dotfiles sync --continue".cyan().bold()
+                );
+
                 // Stop execution to protect the Home directory from conflict markers
-                return Err(e); 
+                return Err(e);
             }
         }
     } else if has_remote && remote_is_empty {
@@ -192,34 +322,63 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
     // --- STEP 4: BACKUP & EXPORT (Repo -> Home) ---
     // We only reach here if Step 3 succeeded (Repo is clean, merged, and valid)
     print_info("Step 4/6: Creating backup of current home files...");
-    let backup_created = backup_home_files(&repo_path, &tracked)?;
+    let backup_created = backup_home_files(&repo_path, &tracked, dry_run)?;
     if backup_created {
-        print_success("Backup created");
+        print_success(if dry_run {
+            "Would create backup"
+        } else {
+            "Backup created"
+        });
     } else {
         print_info("No files to backup (first sync or files don't exist)");
     }
-    
+
     print_info("Step 5/6: Exporting to Home directory...");
-    sync_repo_to_home(&manager, &tracked, encryption_key.as_ref())?;
+    sync_repo_to_home(
+        &manager,
+        &tracked,
+        encryption_key.as_ref(),
+        dry_run,
+        checksum,
+    )?;
 
     // Note: Backups are local-only (in .gitignore), not committed
 
     // --- STEP 6: PUSH ---
-    if has_remote {
+    if dry_run {
+        if has_remote {
+            print_info("Step 6/6: (dry run) Would push to remote.");
+        } else {
+            print_info("Step 6/6: (dry run) No remote configured, nothing to push.");
+        }
+    } else if has_remote {
         print_info("Step 6/6: Pushing to remote (including backups)...");
-        
+
         // Use push with upstream tracking for first push to empty remote
         if remote_is_empty {
-            git.push_set_upstream("origin", &branch)?;
+            git.push_set_upstream_with_progress("origin", &branch, &mut render_progress)?;
             print_success("Pushed successfully (set upstream tracking)");
         } else {
-            git.push("origin", &branch)?;
+            git.push_with_progress("origin", &branch, &mut render_progress)?;
             print_success("Pushed successfully");
         }
     }
 
+    // Persist whatever the import step learned, so the next run can skip decrypting
+    // files that haven't changed. Skipped during a dry run, which promises not to touch
+    // anything on disk beyond what's strictly necessary to preview the sync.
+    if !dry_run {
+        if let Some(cache) = sync_cache.as_ref() {
+            cache.save(&repo_path)?;
+        }
+    }
+
     println!();
-    print_success("Sync completed successfully!");
+    print_success(if dry_run {
+        "Dry run complete — nothing was changed."
+    } else {
+        "Sync completed successfully!"
+    });
     Ok(())
 }
 
@@ -228,87 +387,125 @@ pub fn execute(dir: Option<PathBuf>, encryption_key_path: Option<PathBuf>, _pass
 fn decrypt_to_temp(repo_path: &Path, encrypted_file: &Path, key: &[u8; 32]) -> Result<()> {
     let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
     std::fs::create_dir_all(&temp_dir)?;
-    
-    let rel_path = encrypted_file.strip_prefix(repo_path)
+
+    let rel_path = encrypted_file
+        .strip_prefix(repo_path)
         .context("Failed to get relative path")?;
-    
+
     let decrypted_name = rel_path.with_extension("");
     let decrypted_path = temp_dir.join(decrypted_name);
-    
+
     if let Some(parent) = decrypted_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    
+
     // Check if this is a conflicted file by trying to extract both versions
     let git = GitRepo::new(repo_path);
     let file_path_str = rel_path.to_string_lossy();
-    
+
     let has_ours = git.get_file_version(&file_path_str, 2).is_ok();
     let has_theirs = git.get_file_version(&file_path_str, 3).is_ok();
-    
+
     if has_ours && has_theirs {
-        // This is a conflicted encrypted file - extract both versions and create merged file with conflict markers
+        // This is a conflicted encrypted file - extract all available versions and
+        // attempt a true diff3 merge, falling back to a two-way concatenation when
+        // the base is missing (add/add conflict) or a version isn't valid UTF-8.
         let ours_encrypted = git.get_file_version(&file_path_str, 2)?;
         let theirs_encrypted = git.get_file_version(&file_path_str, 3)?;
-        
-        // Write encrypted versions to temp files
-        let temp_ours_enc = std::env::temp_dir().join(format!("dotfiles_ours_{}", uuid::Uuid::new_v4()));
-        let temp_theirs_enc = std::env::temp_dir().join(format!("dotfiles_theirs_{}", uuid::Uuid::new_v4()));
-        std::fs::write(&temp_ours_enc, &ours_encrypted)?;
-        std::fs::write(&temp_theirs_enc, &theirs_encrypted)?;
-        
-        // Decrypt both versions
-        let temp_ours_dec = std::env::temp_dir().join(format!("dotfiles_ours_dec_{}", uuid::Uuid::new_v4()));
-        let temp_theirs_dec = std::env::temp_dir().join(format!("dotfiles_theirs_dec_{}", uuid::Uuid::new_v4()));
-        
-        FileEncryptor::decrypt_file(&temp_ours_enc, &temp_ours_dec, key)?;
-        FileEncryptor::decrypt_file(&temp_theirs_enc, &temp_theirs_dec, key)?;
-        
-        // Read decrypted content
-        let ours_content = std::fs::read_to_string(&temp_ours_dec)
-            .unwrap_or_else(|_| String::from("<binary content>"));
-        let theirs_content = std::fs::read_to_string(&temp_theirs_dec)
-            .unwrap_or_else(|_| String::from("<binary content>"));
-        
-        // Create merged file with conflict markers
-        let merged_content = format!(
-            "<<<<<<< HEAD (ours - current)\n{}=======\n{}>>>>>>> theirs (incoming)\n",
-            ours_content,
-            theirs_content
-        );
-        
+        let base_encrypted = git.get_file_version(&file_path_str, 1).ok();
+
+        let ours_bytes = decrypt_version(&ours_encrypted, key, "ours")?;
+        let theirs_bytes = decrypt_version(&theirs_encrypted, key, "theirs")?;
+
+        // Missing base (add/add conflict) and a base that fails to decrypt both
+        // degrade the same way: no base means no diff3 merge, fall back to the
+        // two-way concatenation below.
+        let merged = base_encrypted
+            .and_then(|base_encrypted| decrypt_version(&base_encrypted, key, "base").ok())
+            .and_then(|base_bytes| {
+                let base_text = std::str::from_utf8(&base_bytes).ok()?;
+                let ours_text = std::str::from_utf8(&ours_bytes).ok()?;
+                let theirs_text = std::str::from_utf8(&theirs_bytes).ok()?;
+                Some(diff3_merge(base_text, ours_text, theirs_text))
+            });
+
+        let merged_content = match merged {
+            Some(merge) if merge.has_conflicts => {
+                print_info(&format!(
+                    "Decrypted conflicted file with diff3 markers to: {}",
+                    decrypted_path.display()
+                ));
+                merge.text
+            }
+            Some(merge) => {
+                print_info(&format!(
+                    "Decrypted and auto-merged non-conflicting changes to: {}",
+                    decrypted_path.display()
+                ));
+                merge.text
+            }
+            None => {
+                let ours_content = bytes_to_display_text(&ours_bytes);
+                let theirs_content = bytes_to_display_text(&theirs_bytes);
+                print_info(&format!(
+                    "Decrypted conflicted file with markers to: {}",
+                    decrypted_path.display()
+                ));
+                format!(
+                    "<<<<<<< HEAD (ours - current)\n{}=======\n{}>>>>>>> theirs (incoming)\n",
+                    ours_content, theirs_content
+                )
+            }
+        };
+
         std::fs::write(&decrypted_path, merged_content)?;
-        
-        // Clean up temp files
-        let _ = std::fs::remove_file(temp_ours_enc);
-        let _ = std::fs::remove_file(temp_theirs_enc);
-        let _ = std::fs::remove_file(temp_ours_dec);
-        let _ = std::fs::remove_file(temp_theirs_dec);
-        
-        print_info(&format!("Decrypted conflicted file with markers to: {}", decrypted_path.display()));
     } else {
         // Not conflicted or can't extract versions - just decrypt as-is
         FileEncryptor::decrypt_file(encrypted_file, &decrypted_path, key)?;
         print_info(&format!("Decrypted to: {}", decrypted_path.display()));
     }
-    
+
     Ok(())
 }
 
+/// Decrypts one indexed, encrypted conflict-stage version (the in-memory blob
+/// returned by `GitRepo::get_file_version`) via a scratch temp file pair, since
+/// `FileEncryptor` operates on paths rather than buffers.
+fn decrypt_version(encrypted: &[u8], key: &[u8; 32], label: &str) -> Result<Vec<u8>> {
+    let temp_enc = std::env::temp_dir().join(format!("dotfiles_{label}_{}", uuid::Uuid::new_v4()));
+    let temp_dec =
+        std::env::temp_dir().join(format!("dotfiles_{label}_dec_{}", uuid::Uuid::new_v4()));
+
+    std::fs::write(&temp_enc, encrypted)?;
+    FileEncryptor::decrypt_file(&temp_enc, &temp_dec, key)?;
+    let content = std::fs::read(&temp_dec)?;
+
+    let _ = std::fs::remove_file(&temp_enc);
+    let _ = std::fs::remove_file(&temp_dec);
+
+    Ok(content)
+}
+
+/// Renders decrypted bytes for display in a conflict-marker fallback, substituting
+/// a placeholder for content that isn't valid UTF-8.
+fn bytes_to_display_text(bytes: &[u8]) -> String {
+    String::from_utf8(bytes.to_vec()).unwrap_or_else(|_| String::from("<binary content>"))
+}
+
 fn cleanup_temp_dir(repo_path: &Path) -> Result<()> {
     let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
-    
+
     if temp_dir.exists() {
         std::fs::remove_dir_all(&temp_dir).ok();
     }
-    
+
     Ok(())
 }
 
 fn resolve_encryption_key(repo_path: &Path) -> Result<[u8; 32]> {
     let has_marker = FileEncryptor::is_encryption_setup(repo_path);
     let has_key = FileEncryptor::has_local_key();
-    
+
     if has_marker && has_key {
         // Load existing key from home directory
         FileEncryptor::load_key_from_home()
@@ -316,33 +513,34 @@ fn resolve_encryption_key(repo_path: &Path) -> Result<[u8; 32]> {
         // Marker exists but no key - need seed phrase
         print_info("Encrypted files detected but encryption key not found in home directory.");
         print_info("Please enter your 12-word seed phrase to restore encryption.");
-        
+
         let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
-        let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic);
+        let key = FileEncryptor::unwrap_key(repo_path, &mnemonic)?;
         FileEncryptor::save_key_to_home(&key)?;
         print_success("Encryption key restored and saved to home directory");
-        
+
         Ok(key)
     } else if !has_marker && check_for_encrypted_files_in_repo(repo_path) {
         // Old repo without marker but has encrypted files
         print_warning("Encrypted files detected but no encryption marker file.");
         print_info("Please enter your 12-word seed phrase to restore encryption.");
-        
+
         let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
-        let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic);
+        let kdf_params = crate::encryption::KdfParams::generate_argon2id();
+        let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic, &kdf_params)?;
         FileEncryptor::save_key_to_home(&key)?;
-        FileEncryptor::create_encryption_marker(repo_path)?;
+        FileEncryptor::create_encryption_marker(repo_path, &kdf_params)?;
         print_success("Encryption key restored and marker file created");
-        
+
         Ok(key)
     } else {
-        bail!("No encrypted files found. Use 'dotfiles add --encrypt <file>' to add encrypted files.");
+        bail!(
+            "No encrypted files found. Use 'dotfiles add --encrypt <file>' to add encrypted files."
+        );
     }
 }
 
 fn check_for_encrypted_files_in_repo(repo_path: &std::path::Path) -> bool {
-    use walkdir::WalkDir;
-    
     for entry in WalkDir::new(repo_path).max_depth(5) {
         if let Ok(entry) = entry {
             if entry.path().extension().and_then(|s| s.to_str()) == Some("enc") {
@@ -353,195 +551,503 @@ fn check_for_encrypted_files_in_repo(repo_path: &std::path::Path) -> bool {
     false
 }
 
-fn backup_home_files(repo_path: &std::path::Path, files: &[TrackedFile]) -> Result<bool> {
+fn backup_home_files(
+    repo_path: &std::path::Path,
+    files: &[TrackedFile],
+    dry_run: bool,
+) -> Result<bool> {
     use std::fs;
-    
+
     // Create timestamp directory name
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let backup_dir = repo_path.join(".backup").join(timestamp.to_string());
-    
+
     let mut any_backed_up = false;
-    
+
     for file in files {
         let home_path = FileSyncer::expand_tilde(&file.path);
-        
+
         // Only backup if file exists in home
         if !home_path.exists() {
             continue;
         }
-        
-        // Skip directories - we only backup files
+
+        let relative_path = file.path.trim_start_matches("~/").trim_start_matches('/');
+
         if home_path.is_dir() {
+            // Back up only the members that are actually tracked (per the
+            // include/exclude rules), same as what sync would touch.
+            let spec = DirSpec::compile(&home_path, file.include_patterns(), file.exclude_patterns())?;
+
+            for entry in WalkDir::new(&home_path).min_depth(1) {
+                let entry = entry?;
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                let relative = entry.path().strip_prefix(&home_path)?;
+                if !spec.matches(relative, false) {
+                    continue;
+                }
+
+                if dry_run {
+                    print_info(&format!("  would back up: {}/{}", file.path, relative.display()));
+                    any_backed_up = true;
+                    continue;
+                }
+
+                let backup_file = backup_dir.join(relative_path).join(relative);
+                if let Some(parent) = backup_file.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+
+                // IMPORTANT: Backups are ALWAYS stored UNENCRYPTED locally
+                // This is safe because .backup/ is in .gitignore (never pushed to remote)
+                // This allows emergency recovery without needing seed phrase
+                FileSyncer::sync_file(entry.path(), &backup_file)?;
+                any_backed_up = true;
+            }
             continue;
         }
-        
+
+        if dry_run {
+            print_info(&format!("  would back up: {}", file.path));
+            any_backed_up = true;
+            continue;
+        }
+
         // Create backup path mirroring the home structure
-        let relative_path = file.path.trim_start_matches("~/").trim_start_matches('/');
         let backup_file = backup_dir.join(relative_path);
-        
+
         // Create parent directory
         if let Some(parent) = backup_file.parent() {
             fs::create_dir_all(parent)?;
         }
-        
+
         // IMPORTANT: Backups are ALWAYS stored UNENCRYPTED locally
         // This is safe because .backup/ is in .gitignore (never pushed to remote)
         // This allows emergency recovery without needing seed phrase
         FileSyncer::sync_file(&home_path, &backup_file)?;
         any_backed_up = true;
     }
-    
+
     // If no files were backed up, remove the empty directory
-    if !any_backed_up && backup_dir.exists() {
+    if !dry_run && !any_backed_up && backup_dir.exists() {
         fs::remove_dir_all(&backup_dir).ok();
     }
-    
+
     Ok(any_backed_up)
 }
 
-fn sync_home_to_repo(manager: &ConfigManager, files: &[TrackedFile], encryption_key: Option<&[u8; 32]>) -> Result<()> {
+fn sync_home_to_repo(
+    manager: &ConfigManager,
+    files: &[TrackedFile],
+    encryption_key: Option<&[u8; 32]>,
+    mut sync_cache: Option<&mut SyncCache>,
+    dry_run: bool,
+    checksum: bool,
+) -> Result<()> {
     let repo_path = manager.get_repo_path();
     let mut synced_count = 0;
 
     for file in files {
         let home_path = FileSyncer::expand_tilde(&file.path);
-        
+
         if !home_path.exists() {
             continue;
         }
-        
-        // Skip directories - we only sync files
+
+        let repo_root = repo_path.join(file.path.trim_start_matches("~/").trim_start_matches('/'));
+
         if home_path.is_dir() {
-            continue;
-        }
-        
-        let repo_file = repo_path.join(file.path.trim_start_matches("~/").trim_start_matches('/'));
-
-        if file.encrypted {
-            if let Some(key) = encryption_key {
-                let encrypted_path = repo_file.with_extension("enc");
-                
-                // Check if file needs syncing (decrypt existing and compare plaintext)
-                let needs_sync = if encrypted_path.exists() {
-                    // Decrypt existing encrypted file to temp and compare with source
-                    let temp_decrypted = std::env::temp_dir().join(format!("dotfiles_temp_{}", uuid::Uuid::new_v4()));
-                    FileEncryptor::decrypt_file(&encrypted_path, &temp_decrypted, key)?;
-                    let is_different = !files_are_identical(&home_path, &temp_decrypted)?;
-                    let _ = std::fs::remove_file(temp_decrypted);
-                    is_different
+            let spec = DirSpec::compile(&home_path, file.include_patterns(), file.exclude_patterns())?;
+
+            for entry in WalkDir::new(&home_path).min_depth(1) {
+                let entry = entry?;
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                let relative = entry.path().strip_prefix(&home_path)?;
+                if !spec.matches(relative, false) {
+                    continue;
+                }
+
+                let cache_key = format!("{}/{}", file.path, relative.display());
+                let repo_file = if file.encrypted {
+                    repo_root.join(encrypted_member_name(relative))
                 } else {
-                    true
+                    repo_root.join(relative)
                 };
-                
-                if needs_sync {
-                    FileEncryptor::encrypt_file(&home_path, &encrypted_path, key)?;
+
+                if import_one_file(
+                    &cache_key,
+                    entry.path(),
+                    &repo_file,
+                    file.encrypted,
+                    encryption_key,
+                    sync_cache.as_deref_mut(),
+                    dry_run,
+                    checksum,
+                )? {
                     synced_count += 1;
                 }
             }
+            continue;
+        }
+
+        let repo_file = if file.encrypted {
+            repo_root.with_extension("enc")
         } else {
-            // Check if non-encrypted file needs syncing
-            let needs_sync = if repo_file.exists() {
-                !files_are_identical(&home_path, &repo_file)?
-            } else {
-                true
-            };
-            
-            if needs_sync {
-                FileSyncer::sync_file(&home_path, &repo_file)?;
-                synced_count += 1;
-            }
+            repo_root
+        };
+
+        if import_one_file(
+            &file.path,
+            &home_path,
+            &repo_file,
+            file.encrypted,
+            encryption_key,
+            sync_cache.as_deref_mut(),
+            dry_run,
+            checksum,
+        )? {
+            synced_count += 1;
         }
     }
-    
+
     if synced_count > 0 {
-        print_info(&format!("Synced {} file(s) with changes", synced_count));
+        print_info(&format!(
+            "{} {} file(s) with changes",
+            if dry_run { "Would sync" } else { "Synced" },
+            synced_count
+        ));
     } else {
         print_info("All files already in sync (no changes)");
     }
-    
+
     Ok(())
 }
 
+/// Imports a single home file into the repo, encrypting it (with an `.enc` sibling)
+/// if `encrypted` is set. Used both for individually tracked files and for each file
+/// expanded out of a tracked directory by `sync_home_to_repo`. Returns whether
+/// anything was actually copied.
+fn import_one_file(
+    cache_key: &str,
+    home_path: &Path,
+    repo_file: &Path,
+    encrypted: bool,
+    encryption_key: Option<&[u8; 32]>,
+    sync_cache: Option<&mut SyncCache>,
+    dry_run: bool,
+    checksum: bool,
+) -> Result<bool> {
+    if encrypted {
+        let Some(key) = encryption_key else {
+            return Ok(false);
+        };
+
+        // Check if file needs syncing (decrypt existing and compare plaintext). A
+        // cache hit skips the decrypt entirely; a miss falls back to the real
+        // decrypt-and-compare and records the outcome for next time.
+        let needs_sync = if repo_file.exists() {
+            let cached = sync_cache
+                .as_deref()
+                .and_then(|cache| cache.check(cache_key, home_path, repo_file));
+
+            if let Some(in_sync) = cached {
+                if checksum && !in_sync {
+                    if let Some(hash) = sync_cache
+                        .as_deref()
+                        .and_then(|cache| cache.content_hash(cache_key))
+                    {
+                        let home_digest = hex(&hash_file(home_path)?);
+                        print_info(&format!(
+                            "  {} checksum mismatch (cached): {} vs {}",
+                            cache_key,
+                            &home_digest[..12],
+                            &hash[..12]
+                        ));
+                    }
+                }
+                !in_sync
+            } else {
+                // Decrypt existing encrypted file to temp and compare with source
+                let temp_decrypted =
+                    std::env::temp_dir().join(format!("dotfiles_temp_{}", uuid::Uuid::new_v4()));
+                FileEncryptor::decrypt_file(repo_file, &temp_decrypted, key)?;
+                let is_different = !files_match(home_path, &temp_decrypted, checksum, cache_key)?;
+                let content_hash = hex(&hash_file(&temp_decrypted)?);
+                let _ = std::fs::remove_file(temp_decrypted);
+
+                if let Some(cache) = sync_cache {
+                    cache.record(cache_key, home_path, repo_file, content_hash, !is_different);
+                }
+
+                is_different
+            }
+        } else {
+            true
+        };
+
+        if needs_sync {
+            if dry_run {
+                print_info(&format!("  would encrypt+import: {}", cache_key));
+            } else {
+                FileEncryptor::encrypt_file(home_path, repo_file, key)?;
+            }
+        }
+
+        Ok(needs_sync)
+    } else {
+        let needs_sync = if repo_file.exists() {
+            !files_match(home_path, repo_file, checksum, cache_key)?
+        } else {
+            true
+        };
+
+        if needs_sync {
+            if dry_run {
+                print_info(&format!("  would import: {}", cache_key));
+            } else {
+                FileSyncer::sync_file(home_path, repo_file)?;
+            }
+        }
+
+        Ok(needs_sync)
+    }
+}
+
+/// Plain byte-buffer comparison, the default mode: cheap, and correct for "identical
+/// or not" without needing to read a file twice.
 fn files_are_identical(path1: &std::path::Path, path2: &std::path::Path) -> Result<bool> {
     use std::io::Read;
-    
+
     let mut file1 = std::fs::File::open(path1)?;
     let mut file2 = std::fs::File::open(path2)?;
-    
+
     let mut buf1 = Vec::new();
     let mut buf2 = Vec::new();
-    
+
     file1.read_to_end(&mut buf1)?;
     file2.read_to_end(&mut buf2)?;
-    
+
     Ok(buf1 == buf2)
 }
 
-fn sync_repo_to_home(manager: &ConfigManager, files: &[TrackedFile], encryption_key: Option<&[u8; 32]>) -> Result<()> {
+/// Compares two files, optionally in "checksum" mode: computes a SHA-256 digest of
+/// each side and prints both (truncated) so a divergence can be audited by eye before
+/// committing to a real sync, instead of just being told "different".
+fn files_match(
+    path1: &std::path::Path,
+    path2: &std::path::Path,
+    checksum: bool,
+    label: &str,
+) -> Result<bool> {
+    if !checksum {
+        return files_are_identical(path1, path2);
+    }
+
+    let digest1 = hex(&hash_file(path1)?);
+    let digest2 = hex(&hash_file(path2)?);
+    let matches = digest1 == digest2;
+
+    if !matches {
+        print_info(&format!(
+            "  {} checksum mismatch: {} vs {}",
+            label,
+            &digest1[..12],
+            &digest2[..12]
+        ));
+    }
+
+    Ok(matches)
+}
+
+fn hash_file(path: &std::path::Path) -> Result<[u8; 32]> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().into())
+}
+
+fn hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sync_repo_to_home(
+    manager: &ConfigManager,
+    files: &[TrackedFile],
+    encryption_key: Option<&[u8; 32]>,
+    dry_run: bool,
+    checksum: bool,
+) -> Result<()> {
     let repo_path = manager.get_repo_path();
     let mut synced_count = 0;
 
     for file in files {
         let home_path = FileSyncer::expand_tilde(&file.path);
-        let repo_file = repo_path.join(file.path.trim_start_matches("~/").trim_start_matches('/'));
+        let repo_root = repo_path.join(file.path.trim_start_matches("~/").trim_start_matches('/'));
 
-        // Skip directories - we only sync files
-        if repo_file.exists() && repo_file.is_dir() {
-            continue;
-        }
+        if repo_root.is_dir() {
+            let spec = DirSpec::compile(&repo_root, file.include_patterns(), file.exclude_patterns())?;
 
-        if file.encrypted {
-            if let Some(key) = encryption_key {
-                let encrypted_path = repo_file.with_extension("enc");
-                if encrypted_path.exists() {
-                    // Create parent directory if it doesn't exist
-                    if let Some(parent) = home_path.parent() {
-                        std::fs::create_dir_all(parent)?;
-                    }
-                    
-                    // Check if decryption is needed (compare decrypted content)
-                    let needs_sync = if home_path.exists() {
-                        // Decrypt to temp and compare
-                        let temp_decrypted = std::env::temp_dir().join(format!("dotfiles_temp_{}", uuid::Uuid::new_v4()));
-                        FileEncryptor::decrypt_file(&encrypted_path, &temp_decrypted, key)?;
-                        let is_different = !files_are_identical(&temp_decrypted, &home_path)?;
-                        let _ = std::fs::remove_file(temp_decrypted);
-                        is_different
-                    } else {
-                        true
-                    };
-                    
-                    if needs_sync {
-                        FileEncryptor::decrypt_file(&encrypted_path, &home_path, key)?;
-                        synced_count += 1;
+            for entry in WalkDir::new(&repo_root).min_depth(1) {
+                let entry = entry?;
+                if entry.file_type().is_dir() {
+                    continue;
+                }
+
+                // For an encrypted directory, the repo side stores `<relative>.enc`;
+                // match the spec (and compute the home path) against the canonical
+                // relative path, not the on-disk `.enc` name.
+                let (relative, repo_file) = if file.encrypted {
+                    match strip_encrypted_member_suffix(
+                        entry.path().strip_prefix(&repo_root)?,
+                    ) {
+                        Some(relative) => (relative, entry.path().to_path_buf()),
+                        None => continue,
                     }
+                } else {
+                    (
+                        entry.path().strip_prefix(&repo_root)?.to_path_buf(),
+                        entry.path().to_path_buf(),
+                    )
+                };
+
+                if !spec.matches(&relative, false) {
+                    continue;
+                }
+
+                let cache_key = format!("{}/{}", file.path, relative.display());
+                if export_one_file(
+                    &cache_key,
+                    &repo_file,
+                    &home_path.join(&relative),
+                    file.encrypted,
+                    encryption_key,
+                    dry_run,
+                    checksum,
+                )? {
+                    synced_count += 1;
                 }
             }
-        } else if repo_file.exists() {
-            // Create parent directory if it doesn't exist
+            continue;
+        }
+
+        let repo_file = if file.encrypted {
+            repo_root.with_extension("enc")
+        } else {
+            repo_root.clone()
+        };
+
+        if export_one_file(
+            &file.path,
+            &repo_file,
+            &home_path,
+            file.encrypted,
+            encryption_key,
+            dry_run,
+            checksum,
+        )? {
+            synced_count += 1;
+        }
+    }
+
+    if synced_count > 0 {
+        print_info(&format!(
+            "{} {} file(s) with changes",
+            if dry_run { "Would export" } else { "Exported" },
+            synced_count
+        ));
+    } else {
+        print_info("All files already in sync (no changes)");
+    }
+
+    Ok(())
+}
+
+/// Exports a single repo file into the home directory, decrypting it if `encrypted`
+/// is set. Used both for individually tracked files and for each file expanded out of
+/// a tracked directory by `sync_repo_to_home`. Returns whether anything was actually
+/// copied.
+fn export_one_file(
+    cache_key: &str,
+    repo_file: &Path,
+    home_path: &Path,
+    encrypted: bool,
+    encryption_key: Option<&[u8; 32]>,
+    dry_run: bool,
+    checksum: bool,
+) -> Result<bool> {
+    if !repo_file.exists() {
+        return Ok(false);
+    }
+
+    if encrypted {
+        let Some(key) = encryption_key else {
+            return Ok(false);
+        };
+
+        if !dry_run {
             if let Some(parent) = home_path.parent() {
                 std::fs::create_dir_all(parent)?;
             }
-            
-            // Check if file needs syncing
-            let needs_sync = if home_path.exists() {
-                !files_are_identical(&repo_file, &home_path)?
+        }
+
+        let needs_sync = if home_path.exists() {
+            let temp_decrypted =
+                std::env::temp_dir().join(format!("dotfiles_temp_{}", uuid::Uuid::new_v4()));
+            FileEncryptor::decrypt_file(repo_file, &temp_decrypted, key)?;
+            let is_different = !files_match(&temp_decrypted, home_path, checksum, cache_key)?;
+            let _ = std::fs::remove_file(temp_decrypted);
+            is_different
+        } else {
+            true
+        };
+
+        if needs_sync {
+            if dry_run {
+                print_info(&format!("  would decrypt+export: {}", cache_key));
             } else {
-                true
-            };
-            
-            if needs_sync {
-                FileSyncer::sync_file(&repo_file, &home_path)?;
-                synced_count += 1;
+                FileEncryptor::decrypt_file(repo_file, home_path, key)?;
             }
         }
-    }
-    
-    if synced_count > 0 {
-        print_info(&format!("Exported {} file(s) with changes", synced_count));
+
+        Ok(needs_sync)
     } else {
-        print_info("All files already in sync (no changes)");
+        if !dry_run {
+            if let Some(parent) = home_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let needs_sync = if home_path.exists() {
+            !files_match(repo_file, home_path, checksum, cache_key)?
+        } else {
+            true
+        };
+
+        if needs_sync {
+            if dry_run {
+                print_info(&format!("  would export: {}", cache_key));
+            } else {
+                FileSyncer::sync_file(repo_file, home_path)?;
+            }
+        }
+
+        Ok(needs_sync)
     }
-    
-    Ok(())
 }
@@ -0,0 +1,34 @@
+use anyhow::{bail, Result};
+use crate::config::ConfigManager;
+use crate::git::GitRepo;
+use crate::utils::{print_error, print_info, print_success};
+
+/// Bails out of an in-progress conflicted `sync`: aborts the rebase via
+/// `git rebase --abort`, then discards the decrypted scratch workspace
+/// `sync --continue` leaves behind under `TEMP_CONFLICTS_DIR`, restoring the repo to
+/// its pre-sync state.
+pub fn execute() -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    if !git.is_in_rebase()? {
+        print_error("Not in a rebase state. There is nothing to abort.");
+        bail!("Not in rebase state");
+    }
+
+    print_info("Aborting sync...");
+    git.rebase_abort()?;
+
+    super::sync_continue::cleanup_temp_dir(&repo_path)?;
+
+    print_success("Sync aborted. The repository has been restored to its pre-sync state.");
+
+    Ok(())
+}
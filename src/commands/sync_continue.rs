@@ -1,17 +1,20 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use crate::config::{ConfigManager, TrackedFile};
-use crate::encryption::FileEncryptor;
+use crate::config::{ConfigManager, RuntimeConfig, TrackedFile};
+use crate::encryption::{Encryptor, FileEncryptor, MnemonicEncryptor};
+use crate::gpg::GpgEncryptor;
 use crate::git::GitRepo;
+use crate::merge::diff3_merge;
 use crate::utils::{print_error, print_info, print_success, print_warning};
 use std::path::Path;
 
-const TEMP_CONFLICTS_DIR: &str = ".dotfiles_conflicts_temp";
+pub(crate) const TEMP_CONFLICTS_DIR: &str = ".dotfiles_conflicts_temp";
 
 pub fn execute() -> Result<()> {
     let repo_path = ConfigManager::resolve_repo_path()?;
     let manager = ConfigManager::new(repo_path.clone());
-    let git = GitRepo::new(&repo_path);
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
 
     if !manager.is_initialized() {
         print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
@@ -37,40 +40,60 @@ pub fn execute() -> Result<()> {
     // Check if there are any conflicts
     if git.has_conflicts()? {
         let conflicted_files = git.get_conflicted_files()?;
-        
-        print_error("There are still unresolved conflicts!");
-        println!("\n{}", "Conflicted files:".yellow().bold());
-        
+
         // Check if any conflicted files are encrypted
-        let encryption_key = get_encryption_key_if_needed(&repo_path, &tracked)?;
-        
+        let encryptor = get_encryptor_if_needed(&repo_path, &tracked, &manager, &runtime_config)?;
+
+        let mut unresolved = Vec::new();
+
         for file in &conflicted_files {
             let full_path = repo_path.join(file);
-            
+
+            if let Some(template) = runtime_config.merge_tool.as_deref() {
+                match resolve_conflict_with_tool(&repo_path, &git, file, encryptor.as_deref(), template) {
+                    Ok(()) => {
+                        print_success(&format!("Resolved via merge tool: {}", file));
+                        continue;
+                    }
+                    Err(e) => {
+                        print_warning(&format!("Merge tool could not resolve {}: {}", file, e));
+                    }
+                }
+            }
+
+            unresolved.push(file.clone());
+
             // Check if this is an encrypted file
             if file.ends_with(".enc") {
                 println!("  {} {}", "✗".red(), file);
-                
+
                 // Decrypt to temp folder for conflict resolution
-                if let Some(key) = encryption_key.as_ref() {
-                    decrypt_to_temp(&repo_path, &full_path, key)?;
+                if let Some(encryptor) = encryptor.as_deref() {
+                    decrypt_to_temp(&repo_path, &full_path, encryptor)?;
                 }
             } else {
                 println!("  {} {}", "✗".red(), file);
             }
         }
-        
-        if encryption_key.is_some() {
-            println!("\n{}", "Encrypted files have been decrypted to:".yellow());
-            println!("  {}", repo_path.join(TEMP_CONFLICTS_DIR).display());
-            println!("\nResolve conflicts in the decrypted files, then:");
-            println!("  1. The changes will be encrypted back automatically");
+
+        if unresolved.is_empty() {
+            print_success("All conflicts resolved via merge tool.");
+        } else {
+            print_error("There are still unresolved conflicts!");
+            println!("\n{}", "Conflicted files:".yellow().bold());
+
+            if encryptor.is_some() {
+                println!("\n{}", "Encrypted files have been decrypted to:".yellow());
+                println!("  {}", repo_path.join(TEMP_CONFLICTS_DIR).display());
+                println!("\nResolve conflicts in the decrypted files, then:");
+                println!("  1. The changes will be encrypted back automatically");
+            }
+
+            println!("\n{}", "After resolving all conflicts, run:".yellow());
+            println!("  {}", "dotfiles sync --continue".cyan().bold());
+
+            bail!("Conflicts must be resolved before continuing");
         }
-        
-        println!("\n{}", "After resolving all conflicts, run:".yellow());
-        println!("  {}", "dotfiles sync --continue".cyan().bold());
-        
-        bail!("Conflicts must be resolved before continuing");
     }
 
     // Check for conflict markers in all files
@@ -90,7 +113,7 @@ pub fn execute() -> Result<()> {
     }
 
     // Process temp decrypted files if they exist
-    process_temp_conflicts(&repo_path, &tracked)?;
+    process_temp_conflicts(&repo_path, &tracked, &manager, &runtime_config)?;
 
     // Add all files
     print_info("Adding resolved files...");
@@ -120,30 +143,49 @@ pub fn execute() -> Result<()> {
     Ok(())
 }
 
-fn get_encryption_key_if_needed(repo_path: &Path, tracked: &[TrackedFile]) -> Result<Option<[u8; 32]>> {
+/// Builds the `Encryptor` conflict resolution should use, dispatching on
+/// `RuntimeConfig::encryption_backend`: `"gpg"` builds a `GpgEncryptor` from the
+/// configured `gpg_recipients`, anything else (including unset) falls back to the
+/// original mnemonic-derived key, prompting for the seed phrase if it isn't cached
+/// in `~/.dotfiles.encryption.key` yet. Returns `None` if no tracked file is
+/// encrypted, so callers can skip decryption/re-encryption entirely.
+pub(crate) fn get_encryptor_if_needed(
+    repo_path: &Path,
+    tracked: &[TrackedFile],
+    manager: &ConfigManager,
+    runtime_config: &RuntimeConfig,
+) -> Result<Option<Box<dyn Encryptor>>> {
     let has_encrypted = tracked.iter().any(|f| f.encrypted);
-    
-    if has_encrypted {
-        let has_marker = FileEncryptor::is_encryption_setup(repo_path);
-        let has_key = FileEncryptor::has_local_key();
-        
-        if has_marker && has_key {
-            Ok(Some(FileEncryptor::load_key_from_home()?))
-        } else if has_marker && !has_key {
-            print_info("Encrypted files detected. Please enter your seed phrase.");
-            let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
-            let key = FileEncryptor::derive_key_from_mnemonic(&mnemonic);
-            FileEncryptor::save_key_to_home(&key)?;
-            Ok(Some(key))
-        } else {
-            Ok(None)
+
+    if !has_encrypted {
+        return Ok(None);
+    }
+
+    match runtime_config.encryption_backend.as_deref() {
+        Some("gpg") => {
+            let recipients = manager.load_gpg_recipients()?;
+            Ok(Some(Box::new(GpgEncryptor { recipients })))
+        }
+        _ => {
+            let has_marker = FileEncryptor::is_encryption_setup(repo_path);
+            let has_key = FileEncryptor::has_local_key();
+
+            if has_marker && has_key {
+                Ok(Some(Box::new(MnemonicEncryptor { key: FileEncryptor::load_key_from_home()? })))
+            } else if has_marker && !has_key {
+                print_info("Encrypted files detected. Please enter your seed phrase.");
+                let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
+                let key = FileEncryptor::unwrap_key(repo_path, &mnemonic)?;
+                FileEncryptor::save_key_to_home(&key)?;
+                Ok(Some(Box::new(MnemonicEncryptor { key })))
+            } else {
+                Ok(None)
+            }
         }
-    } else {
-        Ok(None)
     }
 }
 
-fn decrypt_to_temp(repo_path: &Path, encrypted_file: &Path, key: &[u8; 32]) -> Result<()> {
+fn decrypt_to_temp(repo_path: &Path, encrypted_file: &Path, encryptor: &dyn Encryptor) -> Result<()> {
     let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
     std::fs::create_dir_all(&temp_dir)?;
     
@@ -168,55 +210,175 @@ fn decrypt_to_temp(repo_path: &Path, encrypted_file: &Path, key: &[u8; 32]) -> R
     let has_theirs = git.get_file_version(&file_path_str, 3).is_ok();
     
     if has_ours && has_theirs {
-        // This is a conflicted encrypted file - extract both versions and create merged file with conflict markers
+        // This is a conflicted encrypted file - extract all available versions and
+        // attempt a true diff3 merge, falling back to a two-way concatenation when
+        // the base is missing (add/add conflict) or a version isn't valid UTF-8.
         let ours_encrypted = git.get_file_version(&file_path_str, 2)?;
         let theirs_encrypted = git.get_file_version(&file_path_str, 3)?;
-        
-        // Write encrypted versions to temp files
-        let temp_ours_enc = std::env::temp_dir().join(format!("dotfiles_ours_{}", uuid::Uuid::new_v4()));
-        let temp_theirs_enc = std::env::temp_dir().join(format!("dotfiles_theirs_{}", uuid::Uuid::new_v4()));
-        std::fs::write(&temp_ours_enc, &ours_encrypted)?;
-        std::fs::write(&temp_theirs_enc, &theirs_encrypted)?;
-        
-        // Decrypt both versions
-        let temp_ours_dec = std::env::temp_dir().join(format!("dotfiles_ours_dec_{}", uuid::Uuid::new_v4()));
-        let temp_theirs_dec = std::env::temp_dir().join(format!("dotfiles_theirs_dec_{}", uuid::Uuid::new_v4()));
-        
-        FileEncryptor::decrypt_file(&temp_ours_enc, &temp_ours_dec, key)?;
-        FileEncryptor::decrypt_file(&temp_theirs_enc, &temp_theirs_dec, key)?;
-        
-        // Read decrypted content
-        let ours_content = std::fs::read_to_string(&temp_ours_dec)
-            .unwrap_or_else(|_| String::from("<binary content>"));
-        let theirs_content = std::fs::read_to_string(&temp_theirs_dec)
-            .unwrap_or_else(|_| String::from("<binary content>"));
-        
-        // Create merged file with conflict markers
-        let merged_content = format!(
-            "<<<<<<< HEAD (ours - current)\n{}=======\n{}>>>>>>> theirs (incoming)\n",
-            ours_content,
-            theirs_content
-        );
-        
+        let base_encrypted = git.get_file_version(&file_path_str, 1).ok();
+
+        let ours_bytes = decrypt_version(&ours_encrypted, encryptor, "ours")?;
+        let theirs_bytes = decrypt_version(&theirs_encrypted, encryptor, "theirs")?;
+
+        // Missing base (add/add conflict) and a base that fails to decrypt both
+        // degrade the same way: no base means no diff3 merge, fall back to the
+        // two-way concatenation below.
+        let merged = base_encrypted
+            .and_then(|base_encrypted| decrypt_version(&base_encrypted, encryptor, "base").ok())
+            .and_then(|base_bytes| {
+                let base_text = std::str::from_utf8(&base_bytes).ok()?;
+                let ours_text = std::str::from_utf8(&ours_bytes).ok()?;
+                let theirs_text = std::str::from_utf8(&theirs_bytes).ok()?;
+                Some(diff3_merge(base_text, ours_text, theirs_text))
+            });
+
+        let merged_content = match merged {
+            Some(merge) if merge.has_conflicts => {
+                print_info(&format!(
+                    "Decrypted conflicted file with diff3 markers to: {}",
+                    decrypted_path.display()
+                ));
+                merge.text
+            }
+            Some(merge) => {
+                print_info(&format!(
+                    "Decrypted and auto-merged non-conflicting changes to: {}",
+                    decrypted_path.display()
+                ));
+                merge.text
+            }
+            None => {
+                let ours_content = bytes_to_display_text(&ours_bytes);
+                let theirs_content = bytes_to_display_text(&theirs_bytes);
+                print_info(&format!(
+                    "Decrypted conflicted file with markers to: {}",
+                    decrypted_path.display()
+                ));
+                format!(
+                    "<<<<<<< HEAD (ours - current)\n{}=======\n{}>>>>>>> theirs (incoming)\n",
+                    ours_content, theirs_content
+                )
+            }
+        };
+
         std::fs::write(&decrypted_path, merged_content)?;
-        
-        // Clean up temp files
-        let _ = std::fs::remove_file(temp_ours_enc);
-        let _ = std::fs::remove_file(temp_theirs_enc);
-        let _ = std::fs::remove_file(temp_ours_dec);
-        let _ = std::fs::remove_file(temp_theirs_dec);
-        
-        print_info(&format!("Decrypted conflicted file with markers to: {}", decrypted_path.display()));
     } else {
         // Not conflicted or can't extract versions - just decrypt as-is
-        FileEncryptor::decrypt_file(encrypted_file, &decrypted_path, key)?;
+        encryptor.decrypt_file(encrypted_file, &decrypted_path)?;
         print_info(&format!("Decrypted to: {}", decrypted_path.display()));
     }
-    
+
     Ok(())
 }
 
-fn check_for_conflict_markers(repo_path: &Path, tracked: &[TrackedFile]) -> Result<Vec<String>> {
+/// Decrypts one indexed, encrypted conflict-stage version (the in-memory blob
+/// returned by `GitRepo::get_file_version`) via a scratch temp file pair, since
+/// `FileEncryptor` operates on paths rather than buffers.
+fn decrypt_version(encrypted: &[u8], encryptor: &dyn Encryptor, label: &str) -> Result<Vec<u8>> {
+    let temp_enc = std::env::temp_dir().join(format!("dotfiles_{label}_{}", uuid::Uuid::new_v4()));
+    let temp_dec =
+        std::env::temp_dir().join(format!("dotfiles_{label}_dec_{}", uuid::Uuid::new_v4()));
+
+    std::fs::write(&temp_enc, encrypted)?;
+    encryptor.decrypt_file(&temp_enc, &temp_dec)?;
+    let content = std::fs::read(&temp_dec)?;
+
+    let _ = std::fs::remove_file(&temp_enc);
+    let _ = std::fs::remove_file(&temp_dec);
+
+    Ok(content)
+}
+
+/// Renders decrypted bytes for display in a conflict-marker fallback, substituting
+/// a placeholder for content that isn't valid UTF-8.
+fn bytes_to_display_text(bytes: &[u8]) -> String {
+    String::from_utf8(bytes.to_vec()).unwrap_or_else(|_| String::from("<binary content>"))
+}
+
+/// Attempts to fully resolve one conflicted file (`file`, repo-relative, as
+/// reported by `GitRepo::get_conflicted_files`) with the configured external merge
+/// tool: decrypts (if encrypted) all three available conflict stages to scratch
+/// temp files, seeds `$output` with our best diff3 attempt so a tool that only
+/// shows a diff still leaves something sane, spawns the tool via
+/// `crate::merge_tool::run`, and — if it leaves no conflict markers behind —
+/// re-encrypts and writes the result back into the repo so the caller's later
+/// `git add -A` picks it up. Returns an error (conflict left unresolved) if the
+/// tool isn't usable, fails, or leaves markers in its output.
+fn resolve_conflict_with_tool(
+    repo_path: &Path,
+    git: &GitRepo,
+    file: &str,
+    encryptor: Option<&dyn Encryptor>,
+    template: &str,
+) -> Result<()> {
+    let encrypted = file.ends_with(".enc");
+
+    let ours_encrypted = git.get_file_version(file, 2)?;
+    let theirs_encrypted = git.get_file_version(file, 3)?;
+    let base_encrypted = git.get_file_version(file, 1).ok();
+
+    let (ours, theirs, base) = if encrypted {
+        let encryptor = encryptor.context("Encrypted conflict, but no encryption key is available")?;
+        (
+            decrypt_version(&ours_encrypted, encryptor, "tool_ours")?,
+            decrypt_version(&theirs_encrypted, encryptor, "tool_theirs")?,
+            base_encrypted.and_then(|base_encrypted| decrypt_version(&base_encrypted, encryptor, "tool_base").ok()),
+        )
+    } else {
+        (ours_encrypted, theirs_encrypted, base_encrypted)
+    };
+
+    let work_dir = std::env::temp_dir().join(format!("dotfiles_mergetool_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work_dir)?;
+
+    let left_path = work_dir.join("left");
+    let base_path = work_dir.join("base");
+    let right_path = work_dir.join("right");
+    let output_path = work_dir.join("output");
+
+    std::fs::write(&left_path, &ours)?;
+    std::fs::write(&right_path, &theirs)?;
+    std::fs::write(&base_path, base.as_deref().unwrap_or(&[]))?;
+
+    let seed = match (
+        base.as_deref().and_then(|b| std::str::from_utf8(b).ok()),
+        std::str::from_utf8(&ours).ok(),
+        std::str::from_utf8(&theirs).ok(),
+    ) {
+        (Some(base_text), Some(ours_text), Some(theirs_text)) => {
+            diff3_merge(base_text, ours_text, theirs_text).text.into_bytes()
+        }
+        _ => ours.clone(),
+    };
+    std::fs::write(&output_path, &seed)?;
+
+    let result = crate::merge_tool::run(template, &left_path, &base_path, &right_path, &output_path);
+
+    let outcome = result.and_then(|()| {
+        let resolved = std::fs::read(&output_path)?;
+        if let Ok(text) = std::str::from_utf8(&resolved) {
+            if text.contains("<<<<<<<") || text.contains("=======") || text.contains(">>>>>>>") {
+                bail!("Merge tool left conflict markers in the output");
+            }
+        }
+
+        let repo_file = repo_path.join(file);
+        if encrypted {
+            let encryptor = encryptor.context("Encrypted conflict, but no encryption key is available")?;
+            encryptor.encrypt_file(&output_path, &repo_file)?;
+        } else {
+            std::fs::write(&repo_file, &resolved)?;
+        }
+
+        Ok(())
+    });
+
+    let _ = std::fs::remove_dir_all(&work_dir);
+
+    outcome
+}
+
+pub(crate) fn check_for_conflict_markers(repo_path: &Path, tracked: &[TrackedFile]) -> Result<Vec<String>> {
     let mut files_with_markers = Vec::new();
     
     for file in tracked {
@@ -246,39 +408,44 @@ fn file_has_conflict_markers(path: &Path) -> Result<bool> {
     Ok(content.contains("<<<<<<<") || content.contains("=======") || content.contains(">>>>>>>"))
 }
 
-fn process_temp_conflicts(repo_path: &Path, tracked: &[TrackedFile]) -> Result<()> {
+fn process_temp_conflicts(
+    repo_path: &Path,
+    tracked: &[TrackedFile],
+    manager: &ConfigManager,
+    runtime_config: &RuntimeConfig,
+) -> Result<()> {
     let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
-    
+
     if !temp_dir.exists() {
         return Ok(());
     }
-    
+
     print_info("Processing temporary decrypted files...");
-    
-    // Get encryption key if needed
-    let encryption_key = get_encryption_key_if_needed(repo_path, tracked)?;
-    
+
+    // Get encryptor if needed
+    let encryptor = get_encryptor_if_needed(repo_path, tracked, manager, runtime_config)?;
+
     for file in tracked {
         if file.encrypted {
             let temp_path = temp_dir.join(file.path.trim_start_matches("~/"));
-            
+
             if temp_path.exists() {
                 // Encrypt back to repo
-                if let Some(key) = encryption_key.as_ref() {
+                if let Some(encryptor) = encryptor.as_deref() {
                     let repo_file = repo_path.join(file.path.trim_start_matches("~/").trim_start_matches('/'));
                     let encrypted_path = repo_file.with_extension("enc");
-                    
-                    FileEncryptor::encrypt_file(&temp_path, &encrypted_path, key)?;
+
+                    encryptor.encrypt_file(&temp_path, &encrypted_path)?;
                     print_success(&format!("Re-encrypted: {}", file.path));
                 }
             }
         }
     }
-    
+
     Ok(())
 }
 
-fn cleanup_temp_dir(repo_path: &Path) -> Result<()> {
+pub(crate) fn cleanup_temp_dir(repo_path: &Path) -> Result<()> {
     let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
     
     if temp_dir.exists() {
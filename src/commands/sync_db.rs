@@ -0,0 +1,69 @@
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+
+use crate::config::ConfigManager;
+use crate::config_source::{self, ConfigSource};
+use crate::utils::{print_error, print_info, print_section, print_success};
+
+/// Imports application-config definitions from one or all registered `ConfigSource`s
+/// into the flat structure under `output` (`<repo>/synced_db` when not given).
+pub fn execute(source: Option<String>, path: Option<PathBuf>, output: Option<PathBuf>) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    let output_dir = output.unwrap_or_else(|| repo_path.join("synced_db"));
+
+    let sources: Vec<Box<dyn ConfigSource>> = match source {
+        Some(name) => {
+            let Some(source) = config_source::by_name(&name, path) else {
+                bail!(
+                    "Unknown config source '{}'. Available: {}",
+                    name,
+                    config_source::all_sources(None)
+                        .iter()
+                        .map(|s| s.name().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            vec![source]
+        }
+        None => config_source::all_sources(path),
+    };
+
+    for source in sources {
+        print_section(&format!("Syncing from '{}'", source.name()));
+
+        let temp_dir = std::env::temp_dir().join(format!("dotfiles_source_{}", source.name()));
+        let fetch_result = source.fetch(&temp_dir);
+
+        let source_root = match fetch_result {
+            Ok(root) => root,
+            Err(e) => {
+                print_error(&format!("Failed to fetch '{}': {}", source.name(), e));
+                continue;
+            }
+        };
+
+        let stats = source.import(&source_root, &output_dir)?;
+        print_success(&format!(
+            "'{}': processed {} applications, skipped {}",
+            source.name(),
+            stats.processed,
+            stats.skipped
+        ));
+
+        if temp_dir.exists() {
+            let _ = std::fs::remove_dir_all(&temp_dir);
+        }
+    }
+
+    print_info(&format!("Synced configuration database written to {}", output_dir.display()));
+
+    Ok(())
+}
@@ -13,7 +13,8 @@ pub fn execute() -> Result<()> {
         bail!("Repository not initialized");
     }
 
-    let git = GitRepo::new(&repo_path);
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
 
     if git.is_in_merge()? {
         print_error("Repository is in the middle of a merge conflict!");
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use crate::config::ConfigManager;
+use crate::git::GitRepo;
+use crate::utils::{print_info, print_section, print_success};
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use super::sync_continue::{check_for_conflict_markers, TEMP_CONFLICTS_DIR};
+
+/// Read-only view of an in-progress conflicted sync: for each tracked file, whether
+/// it is still conflicted in the index, whether it still contains conflict markers,
+/// and whether a decrypted temp copy exists under `TEMP_CONFLICTS_DIR` — so a user
+/// mid-merge can tell exactly what remains before choosing `sync --continue` or
+/// `sync-abort`.
+pub fn execute() -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(&repo_path, runtime_config.git_backend, runtime_config.git_hardening);
+
+    if !git.is_in_rebase()? {
+        print_info("Not in the middle of a sync. Nothing to report.");
+        return Ok(());
+    }
+
+    let tracked = manager.load_tracked_files()?;
+    let conflicted_files = if git.has_conflicts()? {
+        git.get_conflicted_files()?
+    } else {
+        Vec::new()
+    };
+    let files_with_markers = check_for_conflict_markers(&repo_path, &tracked)?;
+    let temp_dir = repo_path.join(TEMP_CONFLICTS_DIR);
+
+    print_section("Conflict Status");
+
+    let mut anything_remaining = false;
+
+    for file in &tracked {
+        let rel = file.path.trim_start_matches("~/").trim_start_matches('/');
+        let repo_rel = if file.encrypted {
+            Path::new(rel).with_extension("enc").to_string_lossy().into_owned()
+        } else {
+            rel.to_string()
+        };
+        let temp_rel = if file.encrypted { Path::new(&repo_rel).with_extension("") } else { PathBuf::from(rel) };
+
+        let conflicted = conflicted_files.iter().any(|f| f == &repo_rel);
+        let has_markers = files_with_markers.iter().any(|f| f == &file.path);
+        let has_temp_copy = temp_dir.join(temp_rel).exists();
+
+        if !conflicted && !has_markers && !has_temp_copy {
+            continue;
+        }
+
+        anything_remaining = true;
+        println!("\n{}", file.path.yellow().bold());
+        println!("  conflicted:         {}", yes_no(conflicted));
+        println!("  has conflict markers: {}", yes_no(has_markers));
+        println!("  decrypted temp copy: {}", yes_no(has_temp_copy));
+    }
+
+    if !anything_remaining {
+        print_success("No conflicts remain. Run 'dotfiles sync --continue' to finish.");
+    } else {
+        println!(
+            "\n{}",
+            "Resolve the files above, then run 'dotfiles sync --continue', or run 'dotfiles sync-abort' to bail out."
+                .dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+fn yes_no(value: bool) -> colored::ColoredString {
+    if value {
+        "yes".red()
+    } else {
+        "no".green()
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::{bail, Result};
+use crate::config::ConfigManager;
+use crate::encryption::FileEncryptor;
+use crate::utils::{print_error, print_info, print_success};
+
+/// Re-derives this repo's encryption key from the user's BIP39 seed phrase and saves
+/// it as the repo-local key (`FileEncryptor::save_key_to_repo`) and as the home key
+/// (`FileEncryptor::save_key_to_home`) that `restore` and `mount` load, so a freshly cloned
+/// repo on a new machine can decrypt `.enc` files without that key ever having been
+/// committed. The counterpart to the seed phrase being the only thing that grants
+/// access once `init` gitignores the repo-local key file.
+pub fn execute() -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    if !FileEncryptor::is_encryption_setup(&repo_path) {
+        print_error("Encryption is not set up for this repository.");
+        bail!("Encryption not set up");
+    }
+
+    print_info("Enter the seed phrase for this repository to unlock encrypted files.");
+    let mnemonic = FileEncryptor::prompt_for_seed_phrase()?;
+    let key = FileEncryptor::unwrap_key(&repo_path, &mnemonic)?;
+
+    FileEncryptor::save_key_to_repo(&repo_path, &key)?;
+    FileEncryptor::save_key_to_home(&key)?;
+    print_success("Encryption key unlocked and saved locally. 'restore'/'add' can now decrypt/encrypt on this machine.");
+
+    Ok(())
+}
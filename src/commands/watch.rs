@@ -0,0 +1,164 @@
+use anyhow::{bail, Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::config::{ConfigManager, TrackedFile};
+use crate::git::GitRepo;
+use crate::sync::FileSyncer;
+use crate::utils::{print_error, print_info, print_success, print_warning};
+
+/// How long to wait after the last filesystem event before treating a burst of changes
+/// as "settled" and running a sync, same rationale as `scan --watch`'s `WATCH_DEBOUNCE`.
+const DEBOUNCE: Duration = Duration::from_millis(1500);
+
+/// Default floor between one auto-sync finishing and the next one starting, so an
+/// editor that touches a file every few seconds doesn't turn into a push-per-keystroke.
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn handle_sigint(_signum: libc::c_int) {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+pub fn execute(interval: Option<u64>) -> Result<()> {
+    let repo_path = ConfigManager::resolve_repo_path()?;
+    let manager = ConfigManager::new(repo_path.clone());
+
+    if !manager.is_initialized() {
+        print_error("Not in a dotfiles repository. Run 'dotfiles init' first.");
+        bail!("Repository not initialized");
+    }
+
+    let runtime_config = manager.load_runtime_config()?;
+    let git = GitRepo::with_backend_and_hardening(
+        &repo_path,
+        runtime_config.git_backend,
+        runtime_config.git_hardening,
+    );
+    if !git.is_repo() {
+        print_error("Not a git repository. Initialize git first.");
+        bail!("Not a git repository");
+    }
+
+    let min_interval = Duration::from_secs(interval.unwrap_or(DEFAULT_INTERVAL_SECS));
+
+    unsafe {
+        libc::signal(libc::SIGINT, handle_sigint as libc::sighandler_t);
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .context("Failed to create filesystem watcher")?;
+
+    let tracked = manager.load_tracked_files()?;
+    if tracked.is_empty() {
+        print_info("No files to track. Use 'dotfiles add' to add files.");
+        return Ok(());
+    }
+    watch_tracked(&mut watcher, &tracked);
+
+    print_info(&format!(
+        "Watching {} tracked path(s); auto-syncing on change (min {}s between syncs). Press Ctrl+C to stop.",
+        tracked.len(),
+        min_interval.as_secs()
+    ));
+
+    let mut pending: HashSet<PathBuf> = HashSet::new();
+    let mut last_event = Instant::now();
+    let mut last_sync: Option<Instant> = None;
+
+    loop {
+        if SHUTDOWN_REQUESTED.load(Ordering::SeqCst) {
+            print_info("Shutting down gracefully...");
+            return Ok(());
+        }
+
+        // A short poll timeout, independent of the debounce window, so a SIGINT that
+        // arrives while nothing is pending still gets noticed promptly.
+        let timeout = if pending.is_empty() {
+            Duration::from_millis(500)
+        } else {
+            DEBOUNCE
+                .saturating_sub(last_event.elapsed())
+                .min(Duration::from_millis(500))
+        };
+
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                pending.extend(event.paths);
+                last_event = Instant::now();
+            }
+            Ok(Err(e)) => {
+                print_warning(&format!("Watcher error: {e}"));
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if pending.is_empty() || last_event.elapsed() < DEBOUNCE {
+                    continue;
+                }
+
+                // Respect --interval as a floor between syncs: rather than interleave a
+                // new sync with one that just finished, hold the queued changes until
+                // the minimum interval has elapsed, then run exactly one sync for
+                // everything that accumulated in the meantime.
+                if let Some(last) = last_sync {
+                    let since = last.elapsed();
+                    if since < min_interval {
+                        continue;
+                    }
+                }
+
+                pending.clear();
+                run_sync();
+                last_sync = Some(Instant::now());
+
+                // The sync's own Step 5/6 (exporting repo changes into the home
+                // directory) re-touches the same tracked paths we're watching, so
+                // drain whatever queued up while it ran rather than treating our
+                // own writes as a fresh change needing another auto-sync.
+                while rx.try_recv().is_ok() {}
+                last_event = Instant::now();
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                bail!("Filesystem watcher channel disconnected");
+            }
+        }
+    }
+}
+
+fn watch_tracked(watcher: &mut RecommendedWatcher, tracked: &[TrackedFile]) {
+    for file in tracked {
+        let home_path = FileSyncer::expand_tilde(&file.path);
+        if !home_path.exists() {
+            continue;
+        }
+        let mode = if home_path.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        // Best-effort: a path that disappears between the existence check and the
+        // watch call just isn't watched this round, not a fatal error.
+        let _ = watcher.watch(&home_path, mode);
+    }
+}
+
+/// Runs the same import -> commit -> pull -> export -> push pipeline as `dotfiles
+/// sync`, reusing it wholesale so there's exactly one implementation of the pipeline
+/// (and of its SAFETY LOCK behavior on rebase conflicts) between the manual and
+/// watch-triggered paths. A failure (e.g. a conflict that engages the safety lock)
+/// is reported and the watcher keeps running; pre-flight's rebase check means it
+/// simply won't sync again until the conflict is resolved with `dotfiles sync --continue`.
+fn run_sync() {
+    print_info("Change detected, running auto-sync...");
+    match crate::commands::sync::execute(None, None, None) {
+        Ok(()) => print_success("Auto-sync complete"),
+        Err(e) => print_warning(&format!("Auto-sync failed: {e}")),
+    }
+}
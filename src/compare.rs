@@ -0,0 +1,134 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Gitignore-style excludes for tracked directories, loaded from a `.dotfilesignore`
+/// at the repo root and (if present) inside the tracked directory itself — the same
+/// layering `.gitignore` does, so a noisy subtree like `node_modules` or a cache
+/// directory inside a tracked config dir can be skipped during comparison and apply.
+pub struct DirIgnore {
+    matcher: ignore::gitignore::Gitignore,
+}
+
+impl DirIgnore {
+    pub fn load(repo_root: &Path, dir: &Path) -> Self {
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+        let _ = builder.add(repo_root.join(".dotfilesignore"));
+        if dir != repo_root {
+            builder.add(dir.join(".dotfilesignore"));
+        }
+        let matcher = builder
+            .build()
+            .unwrap_or_else(|_| ignore::gitignore::GitignoreBuilder::new(dir).build().expect("empty gitignore builder never fails"));
+        Self { matcher }
+    }
+
+    /// No-op matcher for callers (like single-file comparisons) that don't need
+    /// `.dotfilesignore` support.
+    pub fn none(dir: &Path) -> Self {
+        let matcher = ignore::gitignore::GitignoreBuilder::new(dir)
+            .build()
+            .expect("empty gitignore builder never fails");
+        Self { matcher }
+    }
+
+    fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        self.matcher.matched(relative, is_dir).is_ignore()
+    }
+}
+
+/// Recursively compares two directory trees: same set of (non-ignored) relative
+/// entries, and identical content for every file, hashed with SHA-256 so large files
+/// don't need to be held in memory twice. Replaces the old `if path.is_dir() { return
+/// true }` shortcut, which reported a tracked directory as in sync even when files
+/// inside it had changed.
+pub fn dirs_are_same(dir1: &Path, dir2: &Path, ignore: &DirIgnore) -> bool {
+    let entries1 = collect_relative_entries(dir1, ignore);
+    let entries2 = collect_relative_entries(dir2, ignore);
+
+    let names1: HashSet<&PathBuf> = entries1.iter().collect();
+    let names2: HashSet<&PathBuf> = entries2.iter().collect();
+    if names1 != names2 {
+        return false;
+    }
+
+    for relative in &entries1 {
+        let p1 = dir1.join(relative);
+        let p2 = dir2.join(relative);
+
+        if p1.is_dir() != p2.is_dir() {
+            return false;
+        }
+        if p1.is_dir() {
+            continue;
+        }
+
+        match (hash_file(&p1), hash_file(&p2)) {
+            (Ok(h1), Ok(h2)) if h1 == h2 => {}
+            _ => return false,
+        }
+    }
+
+    true
+}
+
+/// Copies `source` into `dest`, skipping any entry matched by `ignore` — the
+/// `.dotfilesignore`-aware counterpart to `FileSyncer::sync_directory`.
+pub fn copy_dir_filtered(source: &Path, dest: &Path, ignore: &DirIgnore) -> std::io::Result<()> {
+    std::fs::create_dir_all(dest)?;
+
+    for entry in WalkDir::new(source).min_depth(1).into_iter().filter_entry(|entry| {
+        let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+        !ignore.is_ignored(relative, entry.file_type().is_dir())
+    }) {
+        let entry = entry?;
+        let relative = match entry.path().strip_prefix(source) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let dest_path = dest.join(relative);
+
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&dest_path)?;
+        } else {
+            if let Some(parent) = dest_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(entry.path(), &dest_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_relative_entries(dir: &Path, ignore: &DirIgnore) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            !ignore.is_ignored(relative, entry.file_type().is_dir())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.path().strip_prefix(dir).ok().map(|p| p.to_path_buf()))
+        .collect()
+}
+
+fn hash_file(path: &Path) -> std::io::Result<[u8; 32]> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(hasher.finalize().into())
+}
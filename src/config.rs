@@ -12,6 +12,38 @@ pub const ENV_LOCAL_CONFIG: &str = "DOTFILES_LOCAL_CONFIG_FILEPATH";
 pub struct DotfilesConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tracked_files: Option<Vec<TrackedFile>>,
+
+    /// Opt-in: store encrypted files under opaque HMAC-derived names instead of their
+    /// plaintext relative paths, so the repo tree doesn't leak what's tracked. Requires
+    /// encryption to already be set up; see `NameManifest`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub encrypt_names: Option<bool>,
+
+    /// Team-wide defaults a repo can pin for machines that haven't overridden them
+    /// locally. Lower-priority than the home/project local config and env vars; see
+    /// `ConfigManager::load_runtime_config`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub use_xdg: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub tag: Option<String>,
+
+    /// Which `GitBackend` to construct (`"shell"` or `"libgit2"`). See
+    /// `crate::git::GitBackendKind`. Defaults to the shell backend when unset or
+    /// unrecognized.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub git_backend: Option<String>,
+
+    /// Opt out of hardening shell `git` invocations against a repo-local
+    /// `.git/config` (disabling fsmonitor/hooks, scrubbing `GIT_*` env). Hardening is
+    /// on by default; set `true` only if you intentionally rely on fsmonitor.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub disable_git_hardening: Option<bool>,
+
+    /// Git URL of this repo's remote, committed so every clone knows where to
+    /// `push`/`pull` without each machine configuring it separately. Set via
+    /// `dotfiles init --remote` or `dotfiles remote set`; see `commands::remote`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub remote: Option<String>,
 }
 
 // Local config - stored in home directory (~/.dotfiles.local.config.json)
@@ -21,16 +53,112 @@ pub struct LocalConfig {
     pub repo_path: PathBuf,
     pub home_path: PathBuf,
     pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub git_backend: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub disable_git_hardening: Option<bool>,
+
+    /// Command template for resolving conflicted files interactively, e.g.
+    /// `"vimdiff $left $base $right -c 'wq $output'"`. `$left`/`$base`/`$right`/
+    /// `$output` are substituted with temp file paths before spawning; see
+    /// `crate::merge_tool`. Unset falls back to the marker-based flow.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub merge_tool: Option<String>,
+
+    /// Community/team stub catalogs to merge into the database on `dotfiles db
+    /// update`, in addition to the embedded defaults and `custom_db`; see
+    /// `crate::db::ConfigDatabase`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remote_stub_sources: Vec<RemoteStubSource>,
+
+    /// Which `Encryptor` backend `dotfiles sync --continue` builds for conflict
+    /// resolution: `"mnemonic"` (default, `crate::encryption::MnemonicEncryptor`) or
+    /// `"gpg"` (`crate::gpg::GpgEncryptor`, using `gpg_recipients`).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub encryption_backend: Option<String>,
+
+    /// GPG recipient key IDs/emails to encrypt to when `encryption_backend` is
+    /// `"gpg"`. Ignored otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpg_recipients: Vec<String>,
+
+    /// How many `.backup/` snapshot batches `rollback` keeps before `apply`/`restore`
+    /// prunes the oldest; see `crate::backup::BackupManifest::prune`. Unset falls back
+    /// to `DEFAULT_BACKUP_RETENTION`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub backup_retention: Option<usize>,
+
+    /// HTTPS credential (e.g. a personal access token) for the `libgit2` backend's
+    /// `pull`/`push`/`remote_has_commits`, offered when a remote's auth challenge accepts
+    /// a username/password (ssh-agent and `~/.ssh/id_*` keys are tried first when it also
+    /// accepts SSH keys). Falls back to the `DOTFILES_GIT_TOKEN` env var when
+    /// unset; see `crate::git::resolve_git_token`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub git_token: Option<String>,
+}
+
+/// Default number of `.backup/` snapshot batches to keep when `backup_retention`
+/// isn't configured.
+pub const DEFAULT_BACKUP_RETENTION: usize = 10;
+
+/// One community/team stub catalog synced by `dotfiles db update`: a git repo laid
+/// out like `custom_db` (`applications/*.conf` + `default_configs/*.conf`), optionally
+/// pinned to a branch, with glob filters narrowing which of its stubs are pulled in.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteStubSource {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub branch: Option<String>,
+
+    /// Gitignore-style glob patterns selecting which of the source's stubs to pull
+    /// in. Empty means every stub is a candidate (subject to `excluded_stubs`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub included_stubs: Vec<String>,
+
+    /// Gitignore-style glob patterns vetoing stubs otherwise selected by
+    /// `included_stubs` (or, absent any, the whole catalog).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excluded_stubs: Vec<String>,
+}
+
+impl RemoteStubSource {
+    /// Filesystem-safe directory name for this source's clone under
+    /// `<repo>/remote_db_cache`, derived from the URL so re-running `db update`
+    /// reuses the same clone instead of re-cloning into a fresh temp dir.
+    pub fn cache_key(&self) -> String {
+        self.url
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    /// Compiles this source's `included_stubs`/`excluded_stubs` into a `DirSpec`,
+    /// matched against stub names the same way `DirSpec` matches relative paths.
+    pub fn filter(&self) -> Result<crate::dir_spec::DirSpec> {
+        crate::dir_spec::DirSpec::compile(Path::new("."), &self.included_stubs, &self.excluded_stubs)
+    }
 }
 
 impl Default for DotfilesConfig {
     fn default() -> Self {
         Self {
             tracked_files: None,
+            encrypt_names: None,
+            use_xdg: None,
+            tag: None,
+            git_backend: None,
+            disable_git_hardening: None,
+            remote: None,
         }
     }
 }
 
+impl DotfilesConfig {
+    pub fn encrypt_names_enabled(&self) -> bool {
+        self.encrypt_names.unwrap_or(false)
+    }
+}
+
 impl Default for LocalConfig {
     fn default() -> Self {
         Self {
@@ -38,10 +166,57 @@ impl Default for LocalConfig {
             repo_path: PathBuf::from("."),
             home_path: dirs::home_dir().unwrap_or_else(|| PathBuf::from("~")),
             tag: None,
+            git_backend: None,
+            disable_git_hardening: None,
+            merge_tool: None,
+            remote_stub_sources: Vec::new(),
+            encryption_backend: None,
+            gpg_recipients: Vec::new(),
+            backup_retention: None,
+            git_token: None,
         }
     }
 }
 
+/// Which layer of the cascading resolution supplied a `RuntimeConfig` field, from
+/// lowest to highest priority. Lets `config --show` explain *why* a value is what it
+/// is instead of just printing the final merged value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    RepoConfig,
+    HomeLocalConfig,
+    ProjectLocalConfig,
+    Env,
+}
+
+impl ConfigSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ConfigSource::Default => "default",
+            ConfigSource::RepoConfig => "repo config (dotfiles.config.json)",
+            ConfigSource::HomeLocalConfig => "home local config",
+            ConfigSource::ProjectLocalConfig => "project-local config",
+            ConfigSource::Env => "environment variable",
+        }
+    }
+}
+
+/// Provenance of each resolved `RuntimeConfig` field, so misconfiguration is
+/// debuggable: which of the layers (default < repo config < home local config <
+/// project-local config < env var) actually won for a given field.
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeConfigProvenance {
+    pub use_xdg: ConfigSource,
+    pub repo_path: ConfigSource,
+    pub home_path: ConfigSource,
+    pub tag: ConfigSource,
+    pub git_backend: ConfigSource,
+    pub git_hardening: ConfigSource,
+    pub merge_tool: ConfigSource,
+    pub encryption_backend: ConfigSource,
+}
+
 // Combined config for runtime use
 #[derive(Debug, Clone)]
 pub struct RuntimeConfig {
@@ -49,13 +224,70 @@ pub struct RuntimeConfig {
     pub repo_path: PathBuf,
     pub home_path: PathBuf,
     pub tag: Option<String>,
+    pub git_backend: crate::git::GitBackendKind,
+    /// Whether shell `git` invocations should be hardened against a repo-local
+    /// `.git/config` (fsmonitor/hooks disabled, `GIT_*` env scrubbed). `true` unless
+    /// `disable_git_hardening` is set.
+    pub git_hardening: bool,
+    /// Command template for an external 3-way merge tool; see `crate::merge_tool`.
+    /// Unset means conflict resolution stays on the marker-based flow.
+    pub merge_tool: Option<String>,
+    /// Which `Encryptor` backend conflict resolution builds; see
+    /// `LocalConfig::encryption_backend`. Unset falls back to the mnemonic backend.
+    pub encryption_backend: Option<String>,
     pub tracked_files: Vec<TrackedFile>,
+    pub provenance: RuntimeConfigProvenance,
+}
+
+/// A single resolved field plus which layer supplied it; used internally while
+/// cascading through the config layers so later layers can override earlier ones.
+struct Layered<T> {
+    value: T,
+    source: ConfigSource,
+}
+
+impl<T> Layered<T> {
+    fn new(value: T, source: ConfigSource) -> Self {
+        Self { value, source }
+    }
+
+    /// Overwrite with `value` from `source` if `value` is `Some`, otherwise leave as-is.
+    fn overlay(&mut self, value: Option<T>, source: ConfigSource) {
+        if let Some(value) = value {
+            self.value = value;
+            self.source = source;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackedFile {
     pub stub: Option<String>,
     pub path: String,
+    #[serde(default)]
+    pub encrypted: bool,
+
+    /// Gitignore-style glob patterns selecting which files under `path` are synced.
+    /// Only meaningful when `path` names a directory; unset means "everything in the
+    /// tree". See `crate::dir_spec::DirSpec`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+
+    /// Glob patterns that veto files otherwise selected by `include` (or, absent an
+    /// `include` list, the whole tree) — e.g. carving a cache or secrets directory out
+    /// of an included config tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+}
+
+impl TrackedFile {
+    pub fn include_patterns(&self) -> &[String] {
+        self.include.as_deref().unwrap_or(&[])
+    }
+
+    pub fn exclude_patterns(&self) -> &[String] {
+        self.exclude.as_deref().unwrap_or(&[])
+    }
 }
 
 pub struct ConfigManager {
@@ -132,16 +364,137 @@ impl ConfigManager {
         }
     }
 
+    /// Walk up from the current directory looking for a project-local
+    /// `.dotfiles.local.config.json`, the way tools like `.editorconfig` resolve
+    /// upward. Distinct from `get_local_config_file_path`, which only ever checks
+    /// `$DOTFILES_LOCAL_CONFIG_FILEPATH` or the home directory.
+    fn find_project_local_config() -> Option<LocalConfig> {
+        let mut dir = std::env::current_dir().ok()?;
+        loop {
+            let candidate = dir.join(DOTFILES_LOCAL_CONFIG);
+            if candidate.exists() {
+                let content = fs::read_to_string(&candidate).ok()?;
+                if let Ok(local) = serde_json::from_str(&content) {
+                    return Some(local);
+                }
+            }
+            if !dir.pop() {
+                return None;
+            }
+        }
+    }
+
+    /// Resolve the runtime config by cascading layers, each overriding the last:
+    /// `LocalConfig::default()` -> repo `dotfiles.config.json` -> home local config ->
+    /// project-local config (found by walking up from the cwd) -> per-field
+    /// environment variables (`DOTFILES_REPO_PATH`, `DOTFILES_USE_XDG`,
+    /// `DOTFILES_TAG`), which win over everything else. The returned `provenance`
+    /// records which layer supplied each field.
     pub fn load_runtime_config(&self) -> Result<RuntimeConfig> {
         let repo_config = self.load_config()?;
-        let local_config = self.load_local_config()?;
-        
+        let home_local_config = self.load_local_config()?;
+        let home_local_exists = self.get_local_config_path().exists();
+        let project_local_config = Self::find_project_local_config();
+
+        let defaults = LocalConfig::default();
+        let mut use_xdg = Layered::new(defaults.use_xdg, ConfigSource::Default);
+        let mut repo_path = Layered::new(defaults.repo_path, ConfigSource::Default);
+        let mut home_path = Layered::new(defaults.home_path, ConfigSource::Default);
+        let mut tag = Layered::new(defaults.tag, ConfigSource::Default);
+        let mut git_backend = Layered::new(crate::git::GitBackendKind::default(), ConfigSource::Default);
+        let mut git_hardening = Layered::new(true, ConfigSource::Default);
+        let mut merge_tool = Layered::new(defaults.merge_tool, ConfigSource::Default);
+        let mut encryption_backend = Layered::new(defaults.encryption_backend, ConfigSource::Default);
+
+        use_xdg.overlay(repo_config.use_xdg, ConfigSource::RepoConfig);
+        tag.overlay(repo_config.tag, ConfigSource::RepoConfig);
+        git_backend.overlay(
+            repo_config.git_backend.as_deref().and_then(crate::git::GitBackendKind::parse),
+            ConfigSource::RepoConfig,
+        );
+        git_hardening.overlay(
+            repo_config.disable_git_hardening.map(|disabled| !disabled),
+            ConfigSource::RepoConfig,
+        );
+
+        use_xdg.overlay(Some(home_local_config.use_xdg).filter(|_| home_local_exists), ConfigSource::HomeLocalConfig);
+        repo_path.overlay(Some(home_local_config.repo_path).filter(|_| home_local_exists), ConfigSource::HomeLocalConfig);
+        home_path.overlay(Some(home_local_config.home_path).filter(|_| home_local_exists), ConfigSource::HomeLocalConfig);
+        tag.overlay(home_local_config.tag, ConfigSource::HomeLocalConfig);
+        git_backend.overlay(
+            home_local_config.git_backend.as_deref().and_then(crate::git::GitBackendKind::parse),
+            ConfigSource::HomeLocalConfig,
+        );
+        git_hardening.overlay(
+            home_local_config.disable_git_hardening.map(|disabled| !disabled),
+            ConfigSource::HomeLocalConfig,
+        );
+        merge_tool.overlay(Some(home_local_config.merge_tool).filter(|_| home_local_exists), ConfigSource::HomeLocalConfig);
+        encryption_backend.overlay(Some(home_local_config.encryption_backend).filter(|_| home_local_exists), ConfigSource::HomeLocalConfig);
+
+        if let Some(project_local_config) = project_local_config {
+            use_xdg.overlay(Some(project_local_config.use_xdg), ConfigSource::ProjectLocalConfig);
+            repo_path.overlay(Some(project_local_config.repo_path), ConfigSource::ProjectLocalConfig);
+            home_path.overlay(Some(project_local_config.home_path), ConfigSource::ProjectLocalConfig);
+            tag.overlay(project_local_config.tag, ConfigSource::ProjectLocalConfig);
+            git_backend.overlay(
+                project_local_config.git_backend.as_deref().and_then(crate::git::GitBackendKind::parse),
+                ConfigSource::ProjectLocalConfig,
+            );
+            git_hardening.overlay(
+                project_local_config.disable_git_hardening.map(|disabled| !disabled),
+                ConfigSource::ProjectLocalConfig,
+            );
+            merge_tool.overlay(Some(project_local_config.merge_tool), ConfigSource::ProjectLocalConfig);
+            encryption_backend.overlay(Some(project_local_config.encryption_backend), ConfigSource::ProjectLocalConfig);
+        }
+
+        if let Ok(env_repo_path) = std::env::var("DOTFILES_REPO_PATH") {
+            repo_path.overlay(Some(PathBuf::from(env_repo_path)), ConfigSource::Env);
+        }
+        if let Ok(env_use_xdg) = std::env::var("DOTFILES_USE_XDG") {
+            if let Ok(parsed) = env_use_xdg.parse::<bool>() {
+                use_xdg.overlay(Some(parsed), ConfigSource::Env);
+            }
+        }
+        if let Ok(env_tag) = std::env::var("DOTFILES_TAG") {
+            tag.overlay(Some(Some(env_tag)), ConfigSource::Env);
+        }
+        if let Ok(env_git_backend) = std::env::var("DOTFILES_GIT_BACKEND") {
+            git_backend.overlay(crate::git::GitBackendKind::parse(&env_git_backend), ConfigSource::Env);
+        }
+        if let Ok(env_disable_hardening) = std::env::var("DOTFILES_DISABLE_GIT_HARDENING") {
+            if let Ok(disabled) = env_disable_hardening.parse::<bool>() {
+                git_hardening.overlay(Some(!disabled), ConfigSource::Env);
+            }
+        }
+        if let Ok(env_merge_tool) = std::env::var("DOTFILES_MERGE_TOOL") {
+            merge_tool.overlay(Some(Some(env_merge_tool)), ConfigSource::Env);
+        }
+        if let Ok(env_encryption_backend) = std::env::var("DOTFILES_ENCRYPTION_BACKEND") {
+            encryption_backend.overlay(Some(Some(env_encryption_backend)), ConfigSource::Env);
+        }
+
         Ok(RuntimeConfig {
-            use_xdg: local_config.use_xdg,
-            repo_path: local_config.repo_path,
-            home_path: local_config.home_path,
-            tag: local_config.tag,
+            use_xdg: use_xdg.value,
+            repo_path: repo_path.value,
+            home_path: home_path.value,
+            tag: tag.value,
+            git_backend: git_backend.value,
+            git_hardening: git_hardening.value,
+            merge_tool: merge_tool.value,
+            encryption_backend: encryption_backend.value,
             tracked_files: repo_config.tracked_files.unwrap_or_default(),
+            provenance: RuntimeConfigProvenance {
+                use_xdg: use_xdg.source,
+                repo_path: repo_path.source,
+                home_path: home_path.source,
+                tag: tag.source,
+                git_backend: git_backend.source,
+                git_hardening: git_hardening.source,
+                merge_tool: merge_tool.source,
+                encryption_backend: encryption_backend.source,
+            },
         })
     }
 
@@ -189,6 +542,57 @@ impl ConfigManager {
                     Some(value.to_string())
                 };
             }
+            "git_backend" => {
+                if value.is_empty() {
+                    local_config.git_backend = None;
+                } else {
+                    crate::git::GitBackendKind::parse(value)
+                        .context("Invalid git_backend value (expected 'shell' or 'libgit2')")?;
+                    local_config.git_backend = Some(value.to_string());
+                }
+            }
+            "disable_git_hardening" => {
+                local_config.disable_git_hardening = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.parse::<bool>().context("Invalid boolean value for disable_git_hardening")?)
+                };
+            }
+            "merge_tool" => {
+                local_config.merge_tool = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "encryption_backend" => {
+                local_config.encryption_backend = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "gpg_recipients" => {
+                local_config.gpg_recipients = if value.is_empty() {
+                    Vec::new()
+                } else {
+                    value.split(',').map(|s| s.trim().to_string()).collect()
+                };
+            }
+            "backup_retention" => {
+                local_config.backup_retention = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.parse::<usize>().context("Invalid integer value for backup_retention")?)
+                };
+            }
+            "git_token" => {
+                local_config.git_token = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
             _ => anyhow::bail!("Unknown config field: {}", field),
         }
         
@@ -211,6 +615,50 @@ impl ConfigManager {
         self.save_config(&config)
     }
 
+    pub fn load_remote_stub_sources(&self) -> Result<Vec<RemoteStubSource>> {
+        Ok(self.load_local_config()?.remote_stub_sources)
+    }
+
+    pub fn add_remote_stub_source(&self, source: RemoteStubSource) -> Result<()> {
+        let mut local_config = self.load_local_config()?;
+        local_config.remote_stub_sources.retain(|s| s.url != source.url);
+        local_config.remote_stub_sources.push(source);
+        self.save_local_config(&local_config)
+    }
+
+    /// Returns `true` if a source matching `url` was found and removed.
+    pub fn remove_remote_stub_source(&self, url: &str) -> Result<bool> {
+        let mut local_config = self.load_local_config()?;
+        let before = local_config.remote_stub_sources.len();
+        local_config.remote_stub_sources.retain(|s| s.url != url);
+        let removed = local_config.remote_stub_sources.len() != before;
+        self.save_local_config(&local_config)?;
+        Ok(removed)
+    }
+
+    /// GPG recipient key IDs/emails configured for the `"gpg"` encryption backend; see
+    /// `LocalConfig::gpg_recipients`.
+    pub fn load_gpg_recipients(&self) -> Result<Vec<String>> {
+        Ok(self.load_local_config()?.gpg_recipients)
+    }
+
+    /// Configured `.backup/` snapshot retention count, falling back to
+    /// `DEFAULT_BACKUP_RETENTION` when unset.
+    pub fn load_backup_retention(&self) -> Result<usize> {
+        Ok(self.load_local_config()?.backup_retention.unwrap_or(DEFAULT_BACKUP_RETENTION))
+    }
+
+    /// Configured remote URL, if `init --remote`/`remote set` has set one. Repo-wide
+    /// (stored in `dotfiles.config.json`, not the per-machine local config).
+    pub fn load_remote(&self) -> Result<Option<String>> {
+        Ok(self.load_config()?.remote)
+    }
+
+    /// HTTPS credential for the `libgit2` backend; see `LocalConfig::git_token`.
+    pub fn load_git_token(&self) -> Result<Option<String>> {
+        Ok(self.load_local_config()?.git_token)
+    }
+
     pub fn is_initialized(&self) -> bool {
         self.get_config_path().exists()
     }
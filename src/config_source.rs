@@ -0,0 +1,277 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Outcome of importing one source into the flat on-disk structure written by
+/// `create_flat_structure`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImportStats {
+    pub processed: usize,
+    pub skipped: usize,
+}
+
+/// A place application-config definitions can come from. `MackupSync` was the only
+/// implementation; this trait lets `sync-db` pick a source by name (or run every
+/// registered one) instead of hardcoding mackup's git clone.
+pub trait ConfigSource {
+    /// Short, stable identifier used on the CLI (`--source <name>`).
+    fn name(&self) -> &str;
+
+    /// Makes the source's data available on disk under `temp_dir`, returning the root
+    /// directory `import` should read from. Network-backed sources (mackup) clone into
+    /// `temp_dir`; purely local sources can just validate and return their own path.
+    fn fetch(&self, temp_dir: &Path) -> Result<PathBuf>;
+
+    /// Parses whatever `fetch` returned and writes it into `output_dir`'s flat
+    /// structure, returning how many application definitions were processed/skipped.
+    fn import(&self, source_root: &Path, output_dir: &Path) -> Result<ImportStats>;
+}
+
+/// Every backend the `sync-db` command knows about. `local_path` is only consumed by
+/// the local-directory source; it's threaded through here rather than stored on the
+/// trait object so the registry stays a plain list of sources.
+pub fn all_sources(local_path: Option<PathBuf>) -> Vec<Box<dyn ConfigSource>> {
+    vec![
+        Box::new(crate::mackup::MackupSource::new()),
+        Box::new(crate::local_source::LocalSource::new(local_path)),
+    ]
+}
+
+/// Looks up a single source by name, e.g. `"mackup"` or `"local"`.
+pub fn by_name(name: &str, local_path: Option<PathBuf>) -> Option<Box<dyn ConfigSource>> {
+    all_sources(local_path)
+        .into_iter()
+        .find(|s| s.name() == name)
+}
+
+/// One path entry from a `configuration_files`/`xdg_configuration_files`/
+/// `configuration_files_exclude` section. Mackup `.cfg` files occasionally annotate an
+/// entry with `path = note` (e.g. an engine/storage hint); rather than discarding those
+/// lines outright, the metadata is kept alongside the path so it round-trips through
+/// the flat structure instead of silently vanishing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CfgEntry {
+    pub path: String,
+    pub metadata: Option<String>,
+}
+
+impl CfgEntry {
+    fn parse(line: &str) -> Self {
+        match line.split_once('=') {
+            Some((path, metadata)) => Self {
+                path: path.trim().to_string(),
+                metadata: Some(metadata.trim().to_string()),
+            },
+            None => Self {
+                path: line.to_string(),
+                metadata: None,
+            },
+        }
+    }
+
+    /// Serializes back to the `path` or `path = metadata` form it was parsed from.
+    fn to_line(&self) -> String {
+        match &self.metadata {
+            Some(meta) => format!("{} = {}", self.path, meta),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Result of parsing one `.cfg` file.
+#[derive(Debug, Default)]
+pub(crate) struct ParsedCfg {
+    pub name: String,
+    pub config_files: Vec<CfgEntry>,
+    pub xdg_files: Vec<CfgEntry>,
+    pub excludes: Vec<CfgEntry>,
+}
+
+impl ParsedCfg {
+    fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.config_files.is_empty()
+            && self.xdg_files.is_empty()
+            && self.excludes.is_empty()
+    }
+}
+
+/// Writes one `applications/<stub>.conf`, `configuration_files/<stub>.conf`,
+/// `xdg_configuration_files/<stub>.conf`, and `excludes/<stub>.conf` per application —
+/// the flat structure every mackup-dialect `ConfigSource` produces, shared so it isn't
+/// duplicated per backend. `xdg_files` are expected to already be resolved to
+/// home-relative paths (see `resolve_xdg_path`) so downstream consumers like
+/// `expand_tilde` can find them directly instead of re-deriving the XDG base dir.
+pub(crate) fn create_flat_structure(
+    stub_name: &str,
+    app_name: &str,
+    config_files: &[CfgEntry],
+    xdg_files: &[CfgEntry],
+    excludes: &[CfgEntry],
+    output_dir: &Path,
+) -> Result<()> {
+    let apps_dir = output_dir.join("applications");
+    let config_files_dir = output_dir.join("configuration_files");
+    let xdg_files_dir = output_dir.join("xdg_configuration_files");
+    let excludes_dir = output_dir.join("excludes");
+
+    fs::create_dir_all(&apps_dir)?;
+    fs::create_dir_all(&config_files_dir)?;
+    fs::create_dir_all(&xdg_files_dir)?;
+    fs::create_dir_all(&excludes_dir)?;
+
+    if !app_name.is_empty() {
+        let app_file = apps_dir.join(format!("{}.conf", stub_name));
+        fs::write(&app_file, format!("name = {}\n", app_name))?;
+    }
+
+    write_entries(
+        &config_files_dir.join(format!("{}.conf", stub_name)),
+        config_files,
+    )?;
+    write_entries(
+        &xdg_files_dir.join(format!("{}.conf", stub_name)),
+        xdg_files,
+    )?;
+    write_entries(&excludes_dir.join(format!("{}.conf", stub_name)), excludes)?;
+
+    Ok(())
+}
+
+fn write_entries(path: &Path, entries: &[CfgEntry]) -> Result<()> {
+    let content = if entries.is_empty() {
+        String::new()
+    } else {
+        entries
+            .iter()
+            .map(CfgEntry::to_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+            + "\n"
+    };
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Resolves an `xdg_configuration_files` entry (given relative to the XDG config base
+/// dir) to a concrete home-relative path, following the XDG base-dir spec:
+/// `$XDG_CONFIG_HOME` if set and non-empty, else `~/.config`. The result is always
+/// expressed as `~/...` (or, if `XDG_CONFIG_HOME` points outside the home directory, an
+/// absolute path) so it's directly usable by `expand_tilde`/`check_stub_sync` instead
+/// of needing a second XDG-aware resolution step downstream.
+pub(crate) fn resolve_xdg_path(raw: &str) -> String {
+    let relative = raw.trim_start_matches('/');
+
+    let xdg_config_home = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")));
+
+    let Some(base) = xdg_config_home else {
+        return format!("~/.config/{relative}");
+    };
+
+    let full = base.join(relative);
+
+    match dirs::home_dir().and_then(|home| full.strip_prefix(&home).ok().map(|p| p.to_path_buf())) {
+        Some(relative_to_home) => format!("~/{}", relative_to_home.display()),
+        None => full.display().to_string(),
+    }
+}
+
+/// Parses mackup's `.cfg` INI dialect: `[application]` (just a `name` key),
+/// `[configuration_files]`, `[xdg_configuration_files]` (resolved against the XDG
+/// base-dir spec), and `[configuration_files_exclude]` negations.
+pub(crate) fn parse_cfg_content(content: &str) -> Result<ParsedCfg> {
+    let mut parsed = ParsedCfg::default();
+    let mut current_section = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            current_section = line[1..line.len() - 1].to_string();
+            continue;
+        }
+
+        match current_section.as_str() {
+            "application" => {
+                if let Some((key, value)) = line.split_once('=') {
+                    if key.trim() == "name" {
+                        parsed.name = value.trim().to_string();
+                    }
+                }
+            }
+            "configuration_files" => parsed.config_files.push(CfgEntry::parse(line)),
+            "xdg_configuration_files" => {
+                let mut entry = CfgEntry::parse(line);
+                entry.path = resolve_xdg_path(&entry.path);
+                parsed.xdg_files.push(entry);
+            }
+            "configuration_files_exclude" => parsed.excludes.push(CfgEntry::parse(line)),
+            _ => {}
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Walks `cfg_dir` for top-level `.cfg` files and imports each one into `output_dir`'s
+/// flat structure. Shared by every `ConfigSource` that speaks mackup's dialect, so
+/// adding a new backend doesn't mean reimplementing the parsing/writing.
+pub(crate) fn import_cfg_dir(cfg_dir: &Path, output_dir: &Path) -> Result<ImportStats> {
+    let cfg_files: Vec<_> = WalkDir::new(cfg_dir)
+        .max_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|s| s.to_str())
+                .map(|s| s == "cfg")
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let mut stats = ImportStats::default();
+
+    for entry in cfg_files {
+        let cfg_path = entry.path();
+        let Some(stub_name) = cfg_path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        match import_one_cfg_file(cfg_path, stub_name, output_dir) {
+            Ok(true) => stats.processed += 1,
+            Ok(false) => stats.skipped += 1,
+            Err(_) => stats.skipped += 1,
+        }
+    }
+
+    Ok(stats)
+}
+
+fn import_one_cfg_file(cfg_path: &Path, stub_name: &str, output_dir: &Path) -> Result<bool> {
+    let content = fs::read_to_string(cfg_path).context("Failed to read .cfg file")?;
+    let parsed = parse_cfg_content(&content)?;
+
+    if parsed.is_empty() {
+        return Ok(false);
+    }
+
+    create_flat_structure(
+        stub_name,
+        &parsed.name,
+        &parsed.config_files,
+        &parsed.xdg_files,
+        &parsed.excludes,
+        output_dir,
+    )?;
+    Ok(true)
+}
@@ -3,35 +3,78 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::OnceLock;
 
+use crate::config::RemoteStubSource;
+use crate::dir_spec::DirSpec;
+
 static DEFAULT_DB: OnceLock<HashMap<String, DefaultStubData>> = OnceLock::new();
 
 const DEFAULT_DB_JSON: &str = include_str!("default_db.json");
 
+/// Subdirectory (under the repo root) each remote stub source is cloned/pulled into,
+/// one directory per source keyed by `RemoteStubSource::cache_key`.
+const REMOTE_DB_CACHE_DIR: &str = "remote_db_cache";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DefaultStubData {
     name: String,
     config_files: Vec<String>,
 }
 
+/// Where a `StubEntry` came from. In merges, custom beats remote, which beats
+/// embedded. Surfaced to callers like `dotfiles list --all` so a stub pulled
+/// in from a community catalog isn't indistinguishable from a built-in one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StubOrigin {
+    Embedded,
+    Custom,
+    Remote(String),
+}
+
+impl StubOrigin {
+    pub fn is_custom(&self) -> bool {
+        matches!(self, StubOrigin::Custom)
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            StubOrigin::Embedded => "default".to_string(),
+            StubOrigin::Custom => "custom".to_string(),
+            StubOrigin::Remote(url) => format!("remote ({url})"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StubEntry {
     pub name: String,
     pub stub: String,
     pub config_files: Vec<String>,
-    pub is_custom: bool,
+    pub origin: StubOrigin,
+}
+
+/// Result of refreshing one `RemoteStubSource`'s cache.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RemoteSyncStats {
+    pub included: usize,
+    pub filtered_out: usize,
 }
 
 pub struct ConfigDatabase {
+    repo_path: PathBuf,
     custom_db_path: PathBuf,
+    remote_sources: Vec<RemoteStubSource>,
 }
 
 impl ConfigDatabase {
     pub fn new(repo_path: &Path) -> Self {
         Self::init_default_db();
         Self {
+            repo_path: repo_path.to_path_buf(),
             custom_db_path: repo_path.join("custom_db"),
+            remote_sources: Vec::new(),
         }
     }
 
@@ -42,10 +85,87 @@ impl ConfigDatabase {
         } else {
             repo_path.join("custom_db")
         };
-        
+
         Self {
+            repo_path: repo_path.to_path_buf(),
             custom_db_path: custom_path,
+            remote_sources: Vec::new(),
+        }
+    }
+
+    /// Registers the configured remote stub sources so `load_stub`/`list_all_stubs`
+    /// fall through to their cached clones between `custom_db` and the embedded
+    /// database. Does not fetch anything itself; see `update_remote`.
+    pub fn with_remote_sources(mut self, sources: Vec<RemoteStubSource>) -> Self {
+        self.remote_sources = sources;
+        self
+    }
+
+    fn remote_cache_root(&self) -> PathBuf {
+        self.repo_path.join(REMOTE_DB_CACHE_DIR)
+    }
+
+    /// Shallow-clones (or, if already cached, pulls) `source` into its cache
+    /// directory and reports how many of its stubs passed the include/exclude
+    /// filters. The clone itself is unfiltered; filtering only affects which stubs
+    /// `load_stub`/`list_all_stubs` expose.
+    pub fn update_remote(&self, source: &RemoteStubSource) -> Result<RemoteSyncStats> {
+        let cache_dir = self.remote_cache_root().join(source.cache_key());
+        fs::create_dir_all(self.remote_cache_root())?;
+
+        if cache_dir.join(".git").exists() {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&cache_dir)
+                .args(["pull", "--ff-only"])
+                .status()
+                .context(format!("Failed to run git pull for {}", source.url))?;
+            if !status.success() {
+                anyhow::bail!("Failed to pull remote stub source {}", source.url);
+            }
+        } else {
+            if cache_dir.exists() {
+                fs::remove_dir_all(&cache_dir)?;
+            }
+            let mut args = vec!["clone".to_string(), "--depth=1".to_string()];
+            if let Some(branch) = &source.branch {
+                args.push("--branch".to_string());
+                args.push(branch.clone());
+            }
+            args.push(source.url.clone());
+            args.push(cache_dir.to_string_lossy().to_string());
+
+            let status = Command::new("git")
+                .args(&args)
+                .status()
+                .context(format!("Failed to clone remote stub source {}", source.url))?;
+            if !status.success() {
+                anyhow::bail!("Failed to clone remote stub source {}", source.url);
+            }
         }
+
+        let filter = source.filter()?;
+        let mut stats = RemoteSyncStats::default();
+        let apps_dir = cache_dir.join("applications");
+        if apps_dir.exists() {
+            for entry in fs::read_dir(&apps_dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("conf") {
+                    continue;
+                }
+                let Some(stub) = path.file_stem().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                if filter.matches(Path::new(stub), false) {
+                    stats.included += 1;
+                } else {
+                    stats.filtered_out += 1;
+                }
+            }
+        }
+
+        Ok(stats)
     }
 
     fn init_default_db() {
@@ -60,29 +180,39 @@ impl ConfigDatabase {
     }
 
     pub fn load_stub(&self, stub: &str) -> Result<Option<StubEntry>> {
-        if let Some(entry) = self.load_stub_from_path(&self.custom_db_path, stub, true)? {
+        if let Some(entry) = self.load_stub_from_path(&self.custom_db_path, stub, StubOrigin::Custom)? {
             return Ok(Some(entry));
         }
-        
+
+        for source in &self.remote_sources {
+            if !source.filter()?.matches(Path::new(stub), false) {
+                continue;
+            }
+            let cache_dir = self.remote_cache_root().join(source.cache_key());
+            if let Some(entry) = self.load_stub_from_path(&cache_dir, stub, StubOrigin::Remote(source.url.clone()))? {
+                return Ok(Some(entry));
+            }
+        }
+
         self.load_stub_from_embedded(stub)
     }
 
     fn load_stub_from_embedded(&self, stub: &str) -> Result<Option<StubEntry>> {
         let db = Self::get_default_db();
-        
+
         if let Some(data) = db.get(stub) {
             Ok(Some(StubEntry {
                 name: data.name.clone(),
                 stub: stub.to_string(),
                 config_files: data.config_files.clone(),
-                is_custom: false,
+                origin: StubOrigin::Embedded,
             }))
         } else {
             Ok(None)
         }
     }
 
-    fn load_stub_from_path(&self, base_path: &Path, stub: &str, is_custom: bool) -> Result<Option<StubEntry>> {
+    fn load_stub_from_path(&self, base_path: &Path, stub: &str, origin: StubOrigin) -> Result<Option<StubEntry>> {
         let applications_path = base_path.join("applications").join(format!("{}.conf", stub));
         
         if !applications_path.exists() {
@@ -104,34 +234,54 @@ impl ConfigDatabase {
             name,
             stub: stub.to_string(),
             config_files,
-            is_custom,
+            origin,
         }))
     }
 
-    pub fn list_all_stubs(&self) -> Result<Vec<String>> {
-        let mut stubs = std::collections::HashSet::new();
-        
-        // Add default stubs from embedded JSON
-        let db = Self::get_default_db();
-        for stub in db.keys() {
-            stubs.insert(stub.clone());
-        }
-        
-        // Add custom stubs from filesystem
-        let apps_dir = self.custom_db_path.join("applications");
+    fn stubs_in_applications_dir(dir: &Path) -> Result<Vec<String>> {
+        let mut stubs = Vec::new();
+        let apps_dir = dir.join("applications");
         if apps_dir.exists() {
             for entry in fs::read_dir(&apps_dir)? {
                 let entry = entry?;
                 let path = entry.path();
-                
+
                 if path.extension().and_then(|s| s.to_str()) == Some("conf") {
                     if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        stubs.insert(stem.to_string());
+                        stubs.push(stem.to_string());
                     }
                 }
             }
         }
-        
+        Ok(stubs)
+    }
+
+    pub fn list_all_stubs(&self) -> Result<Vec<String>> {
+        let mut stubs = std::collections::HashSet::new();
+
+        // Add default stubs from embedded JSON
+        let db = Self::get_default_db();
+        for stub in db.keys() {
+            stubs.insert(stub.clone());
+        }
+
+        // Add stubs from cached remote sources, filtered by each source's
+        // include/exclude globs
+        for source in &self.remote_sources {
+            let filter = source.filter()?;
+            let cache_dir = self.remote_cache_root().join(source.cache_key());
+            for stub in Self::stubs_in_applications_dir(&cache_dir)? {
+                if filter.matches(Path::new(&stub), false) {
+                    stubs.insert(stub);
+                }
+            }
+        }
+
+        // Add custom stubs from filesystem
+        for stub in Self::stubs_in_applications_dir(&self.custom_db_path)? {
+            stubs.insert(stub);
+        }
+
         let mut result: Vec<_> = stubs.into_iter().collect();
         result.sort();
         Ok(result)
@@ -164,18 +314,18 @@ impl ConfigDatabase {
             .collect())
     }
 
-    pub fn get_stub_info(&self, stub: &str) -> Result<Option<(String, Vec<String>, bool)>> {
+    pub fn get_stub_info(&self, stub: &str) -> Result<Option<(String, Vec<String>, StubOrigin)>> {
         let entry = self.load_stub(stub)?;
-        
+
         Ok(entry.map(|e| {
-            (e.name, e.config_files.clone(), e.is_custom)
+            (e.name, e.config_files.clone(), e.origin)
         }))
     }
 
     pub fn get_default_stubs(&self) -> Result<HashMap<String, StubEntry>> {
         let db = Self::get_default_db();
         let mut result = HashMap::new();
-        
+
         for (stub_name, data) in db.iter() {
             result.insert(
                 stub_name.clone(),
@@ -183,11 +333,11 @@ impl ConfigDatabase {
                     name: data.name.clone(),
                     stub: stub_name.clone(),
                     config_files: data.config_files.clone(),
-                    is_custom: false,
+                    origin: StubOrigin::Embedded,
                 }
             );
         }
-        
+
         Ok(result)
     }
 
@@ -205,7 +355,7 @@ impl ConfigDatabase {
             
             if path.extension().and_then(|s| s.to_str()) == Some("conf") {
                 if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                    if let Some(stub_entry) = self.load_stub_from_path(&self.custom_db_path, stem, true)? {
+                    if let Some(stub_entry) = self.load_stub_from_path(&self.custom_db_path, stem, StubOrigin::Custom)? {
                         result.insert(stem.to_string(), stub_entry);
                     }
                 }
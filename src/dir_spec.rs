@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Compiled include/exclude glob rules for a tracked directory, so a directory can be
+/// expanded into the individual files it actually wants synced instead of being
+/// skipped outright or copied wholesale. Patterns use gitignore glob syntax and are
+/// matched against paths relative to the tracked directory's root.
+pub struct DirSpec {
+    include: Option<Gitignore>,
+    exclude: Option<Gitignore>,
+}
+
+impl DirSpec {
+    /// Compiles `include`/`exclude` pattern lists rooted at `dir`. Either list may be
+    /// empty; an empty `include` means "everything is a candidate" rather than
+    /// "nothing is".
+    pub fn compile(dir: &Path, include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: Self::build(dir, include)?,
+            exclude: Self::build(dir, exclude)?,
+        })
+    }
+
+    fn build(dir: &Path, patterns: &[String]) -> Result<Option<Gitignore>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = GitignoreBuilder::new(dir);
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .context(format!("Invalid glob pattern: {pattern}"))?;
+        }
+        Ok(Some(builder.build()?))
+    }
+
+    /// Whether `relative` (a path relative to the tracked directory's root) should be
+    /// synced: matched by `include` (when any include patterns were given) and not
+    /// matched by `exclude`.
+    pub fn matches(&self, relative: &Path, is_dir: bool) -> bool {
+        if let Some(include) = &self.include {
+            if !include.matched(relative, is_dir).is_ignore() {
+                return false;
+            }
+        }
+        if let Some(exclude) = &self.exclude {
+            if exclude.matched(relative, is_dir).is_ignore() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Repo-side filename for one file of a tracked, encrypted directory: the relative
+/// path with a literal `.enc` suffix appended. Unlike a singly tracked file (which
+/// replaces its extension with `.enc`), appending is required here — a directory can
+/// hold files of many different extensions, and replacing would make the original
+/// extension unrecoverable when decrypting back out.
+pub fn encrypted_member_name(relative: &Path) -> PathBuf {
+    let mut name = relative.as_os_str().to_os_string();
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Reverses `encrypted_member_name`. Returns `None` if `path` doesn't end in `.enc`
+/// (e.g. a stray file sitting in the tracked directory that isn't one we wrote).
+pub fn strip_encrypted_member_suffix(path: &Path) -> Option<PathBuf> {
+    let name = path.to_str()?;
+    name.strip_suffix(".enc").map(PathBuf::from)
+}
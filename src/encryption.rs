@@ -1,22 +1,277 @@
-use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
-    Aes256Gcm, Nonce,
-};
+use aes_gcm::Aes256Gcm;
 use anyhow::{bail, Context, Result};
+use argon2::{Algorithm, Argon2, Params as Argon2Params, Version};
 use bip39::{Language, Mnemonic};
+use chacha20poly1305::ChaCha20Poly1305;
+use aead::{Aead, KeyInit, OsRng, Payload};
+use aead::generic_array::GenericArray;
 use pbkdf2::pbkdf2_hmac;
 use rand::RngCore;
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, Write};
 use std::path::{Path, PathBuf};
 
 const NONCE_SIZE: usize = 12;
 const KEY_SIZE: usize = 32;
-const PBKDF2_ITERATIONS: u32 = 100_000;
+const SALT_SIZE: usize = 16;
+// Legacy constants kept only so repos created before the Argon2id migration still decrypt.
+const PBKDF2_LEGACY_ITERATIONS: u32 = 100_000;
+const PBKDF2_LEGACY_SALT: &[u8] = b"dotfiles-encryption";
+// Argon2id defaults (OWASP-recommended floor): 19 MiB memory, 2 passes, single lane.
+const ARGON2_DEFAULT_MEMORY_KIB: u32 = 19_456;
+const ARGON2_DEFAULT_ITERATIONS: u32 = 2;
+const ARGON2_DEFAULT_PARALLELISM: u32 = 1;
 // Key stored in HOME directory for security - NEVER in repo!
 const ENCRYPTION_KEY_FILE: &str = ".dotfiles.encryption.key";
 // Marker file in repo to indicate encryption is used
 const ENCRYPTION_MARKER_FILE: &str = ".dotfiles.encryption.enabled";
+// Repo-local copy of the key, scoped to this one repo rather than the whole machine.
+// Gitignored by `init` so it never leaves this machine; see `unlock`.
+const REPO_ENCRYPTION_KEY_FILE: &str = ".dotfiles.encryption.key";
+
+/// Key derivation function used to turn a mnemonic/passphrase into an encryption key.
+/// Recorded in the marker file so any machine can reproduce the key with the same cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KdfId {
+    Argon2id,
+    /// Kept only to keep pre-migration repos decryptable.
+    Pbkdf2Sha256,
+}
+
+/// KDF parameters persisted in the encryption marker file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub kdf: KdfId,
+    /// Base64-encoded random salt, unique per repo.
+    pub salt: String,
+    pub iterations: u32,
+    /// Argon2 memory cost in KiB; unused for PBKDF2.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub memory_kib: Option<u32>,
+    /// Argon2 parallelism (lanes); unused for PBKDF2.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub parallelism: Option<u32>,
+}
+
+impl KdfParams {
+    /// Generate fresh Argon2id parameters with a random per-repo salt.
+    pub fn generate_argon2id() -> Self {
+        let mut salt = [0u8; SALT_SIZE];
+        OsRng.fill_bytes(&mut salt);
+
+        Self {
+            kdf: KdfId::Argon2id,
+            salt: base64::encode(salt),
+            iterations: ARGON2_DEFAULT_ITERATIONS,
+            memory_kib: Some(ARGON2_DEFAULT_MEMORY_KIB),
+            parallelism: Some(ARGON2_DEFAULT_PARALLELISM),
+        }
+    }
+
+    /// Parameters matching the original hardcoded PBKDF2 scheme, for repos that predate
+    /// the Argon2id migration and whose marker file has no KDF metadata at all.
+    fn legacy_pbkdf2() -> Self {
+        Self {
+            kdf: KdfId::Pbkdf2Sha256,
+            salt: base64::encode(PBKDF2_LEGACY_SALT),
+            iterations: PBKDF2_LEGACY_ITERATIONS,
+            memory_kib: None,
+            parallelism: None,
+        }
+    }
+
+    fn salt_bytes(&self) -> Result<Vec<u8>> {
+        base64::decode(&self.salt).context("Failed to decode KDF salt")
+    }
+}
+
+/// One recipient's wrapped copy of the shared data-encryption key: their own KDF
+/// parameters plus that key, encrypted (`encrypt_data`) under their personally-derived
+/// key. Lets several machines each unlock the same files from their own seed phrase
+/// without any of them ever sharing it with the others.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    pub label: String,
+    pub kdf: KdfParams,
+    pub wrapped: Vec<u8>,
+}
+
+/// Encryption marker file contents: a human-readable notice plus either the legacy
+/// single KDF (pre-multi-recipient repos, where the mnemonic-derived key *is* the file
+/// key) or a list of per-recipient wrapped copies of the shared data-encryption key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptionMarker {
+    notice: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    kdf: Option<KdfParams>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    recipients: Vec<WrappedKey>,
+}
+
+// On-disk header: magic byte, format-version byte, algorithm tag, then nonce, then ciphertext.
+const FORMAT_MAGIC: u8 = 0xD0;
+const FORMAT_VERSION: u8 = 1;
+const HEADER_SIZE: usize = 3;
+
+// Chunked on-disk format used by encrypt_file/decrypt_file: magic, format version 2,
+// algorithm tag, chunk size (u32 BE), nonce prefix, then one or more encrypted chunks.
+const FORMAT_VERSION_CHUNKED: u8 = 2;
+const CHUNK_HEADER_SIZE: usize = HEADER_SIZE + 4;
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+const NONCE_PREFIX_SIZE: usize = NONCE_SIZE - 4;
+const AEAD_TAG_SIZE: usize = 16;
+
+/// AEAD algorithm used to encrypt a file. Stored as a one-byte tag in the file header
+/// so files encrypted under an older/different default can still be decrypted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionType {
+    AesGcm,
+    ChaCha20Poly1305,
+}
+
+impl EncryptionType {
+    fn tag(self) -> u8 {
+        match self {
+            EncryptionType::AesGcm => 0,
+            EncryptionType::ChaCha20Poly1305 => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Result<Self> {
+        match tag {
+            0 => Ok(EncryptionType::AesGcm),
+            1 => Ok(EncryptionType::ChaCha20Poly1305),
+            other => bail!("Unknown encryption algorithm tag: {}", other),
+        }
+    }
+}
+
+impl Default for EncryptionType {
+    fn default() -> Self {
+        EncryptionType::AesGcm
+    }
+}
+
+/// Read up to `buf.len()` bytes, looping over short reads, and return the number of
+/// bytes actually read (fewer than `buf.len()` only at EOF).
+fn read_chunk(reader: &mut impl Read, buf: &mut [u8]) -> Result<usize> {
+    let mut total = 0;
+    while total < buf.len() {
+        let n = reader.read(&mut buf[total..])?;
+        if n == 0 {
+            break;
+        }
+        total += n;
+    }
+    Ok(total)
+}
+
+fn chunk_nonce(prefix: &[u8; NONCE_PREFIX_SIZE], index: u32) -> [u8; NONCE_SIZE] {
+    let mut nonce = [0u8; NONCE_SIZE];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_SIZE..].copy_from_slice(&index.to_be_bytes());
+    nonce
+}
+
+/// AAD binding a chunk to its position: block index plus a final-block flag, so chunk
+/// reordering, truncation, or splicing is caught by AEAD tag verification on decrypt.
+fn chunk_aad(index: u32, is_final: bool) -> [u8; 5] {
+    let mut aad = [0u8; 5];
+    aad[..4].copy_from_slice(&index.to_be_bytes());
+    aad[4] = is_final as u8;
+    aad
+}
+
+fn encrypt_chunk(algo: EncryptionType, key: &[u8; KEY_SIZE], nonce_bytes: &[u8; NONCE_SIZE], plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = GenericArray::from_slice(nonce_bytes);
+    let payload = Payload { msg: plaintext, aad };
+
+    match algo {
+        EncryptionType::AesGcm => {
+            let cipher = Aes256Gcm::new_from_slice(key)
+                .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+            cipher.encrypt(nonce, payload)
+                .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))
+        }
+        EncryptionType::ChaCha20Poly1305 => {
+            let cipher = ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+            cipher.encrypt(nonce, payload)
+                .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))
+        }
+    }
+}
+
+fn decrypt_chunk(algo: EncryptionType, key: &[u8; KEY_SIZE], nonce_bytes: &[u8; NONCE_SIZE], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let nonce = GenericArray::from_slice(nonce_bytes);
+    let payload = Payload { msg: ciphertext, aad };
+
+    match algo {
+        EncryptionType::AesGcm => {
+            let cipher = Aes256Gcm::new_from_slice(key)
+                .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+            cipher.decrypt(nonce, payload)
+                .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))
+        }
+        EncryptionType::ChaCha20Poly1305 => {
+            let cipher = ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+            cipher.decrypt(nonce, payload)
+                .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))
+        }
+    }
+}
+
+/// Files written without the `FORMAT_MAGIC` header predate the authenticated
+/// AES-256-GCM/ChaCha20Poly1305 format entirely (an older, unauthenticated scheme with
+/// no versioned header at all, so corruption or tampering could silently produce
+/// garbage plaintext). There's no safe way to guess their layout, so refuse to decrypt
+/// them and point the user at re-encrypting instead of risking a garbage "decrypt".
+fn bail_on_legacy_unauthenticated_format(source: &Path) -> Result<()> {
+    bail!(
+        "{} does not start with the authenticated encryption header and can't be decrypted. \
+         This usually means the file was written by a version of this tool that predates \
+         tamper-evident encryption; re-add the underlying file with `dotfiles add --encrypt` \
+         to re-encrypt it in the current format.",
+        source.display()
+    );
+}
+
+/// A backend capable of encrypting/decrypting files in place of a raw symmetric key,
+/// so callers that only need "encrypt this path to that path" (like the sync-continue
+/// conflict flow) aren't hardcoded to `FileEncryptor`'s mnemonic-derived key. See
+/// `crate::gpg::GpgEncryptor` for the GPG-recipient alternative to `MnemonicEncryptor`.
+pub trait Encryptor {
+    fn encrypt_file(&self, source: &Path, dest: &Path) -> Result<()>;
+    fn decrypt_file(&self, source: &Path, dest: &Path) -> Result<()>;
+
+    /// Whether this backend is ready to use for `repo_path` (e.g. a key is loaded, or
+    /// recipients are configured) without prompting the user first.
+    fn is_setup(&self, repo_path: &Path) -> bool;
+}
+
+/// The original, default `Encryptor`: a single symmetric key shared by every
+/// recipient, derived from a BIP39 seed phrase. Just forwards to `FileEncryptor`'s
+/// key-based methods.
+pub struct MnemonicEncryptor {
+    pub key: [u8; KEY_SIZE],
+}
+
+impl Encryptor for MnemonicEncryptor {
+    fn encrypt_file(&self, source: &Path, dest: &Path) -> Result<()> {
+        FileEncryptor::encrypt_file(source, dest, &self.key)
+    }
+
+    fn decrypt_file(&self, source: &Path, dest: &Path) -> Result<()> {
+        FileEncryptor::decrypt_file(source, dest, &self.key)
+    }
+
+    fn is_setup(&self, repo_path: &Path) -> bool {
+        FileEncryptor::is_encryption_setup(repo_path)
+    }
+}
 
 pub struct FileEncryptor;
 
@@ -49,17 +304,43 @@ impl FileEncryptor {
     pub fn generate_mnemonic() -> Result<Mnemonic> {
         let mut entropy = [0u8; 16]; // 16 bytes = 128 bits = 12 words
         OsRng.fill_bytes(&mut entropy);
-        
+
         Mnemonic::from_entropy_in(Language::English, &entropy)
             .map_err(|e| anyhow::anyhow!("Failed to generate mnemonic: {}", e))
     }
 
-    /// Derive encryption key from mnemonic seed phrase
-    pub fn derive_key_from_mnemonic(mnemonic: &Mnemonic) -> [u8; KEY_SIZE] {
+    /// Derive encryption key from mnemonic seed phrase using the given KDF parameters
+    pub fn derive_key_from_mnemonic(mnemonic: &Mnemonic, params: &KdfParams) -> Result<[u8; KEY_SIZE]> {
         let seed = mnemonic.to_seed("");
+        Self::derive_key(&seed[..32], params)
+    }
+
+    /// Derive encryption key from a raw passphrase using the given KDF parameters
+    pub fn derive_key_from_passphrase(passphrase: &str, params: &KdfParams) -> Result<[u8; KEY_SIZE]> {
+        Self::derive_key(passphrase.as_bytes(), params)
+    }
+
+    fn derive_key(input: &[u8], params: &KdfParams) -> Result<[u8; KEY_SIZE]> {
+        let salt = params.salt_bytes()?;
         let mut key = [0u8; KEY_SIZE];
-        pbkdf2_hmac::<Sha256>(&seed[..32], b"dotfiles-encryption", PBKDF2_ITERATIONS, &mut key);
-        key
+
+        match params.kdf {
+            KdfId::Argon2id => {
+                let memory_kib = params.memory_kib.unwrap_or(ARGON2_DEFAULT_MEMORY_KIB);
+                let parallelism = params.parallelism.unwrap_or(ARGON2_DEFAULT_PARALLELISM);
+                let argon2_params = Argon2Params::new(memory_kib, params.iterations, parallelism, Some(KEY_SIZE))
+                    .map_err(|e| anyhow::anyhow!("Invalid Argon2id parameters: {}", e))?;
+                let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
+                argon2
+                    .hash_password_into(input, &salt, &mut key)
+                    .map_err(|e| anyhow::anyhow!("Argon2id key derivation failed: {}", e))?;
+            }
+            KdfId::Pbkdf2Sha256 => {
+                pbkdf2_hmac::<Sha256>(input, &salt, params.iterations, &mut key);
+            }
+        }
+
+        Ok(key)
     }
 
     /// Save encryption key to HOME directory (NEVER to repo!)
@@ -71,28 +352,180 @@ impl FileEncryptor {
         Ok(())
     }
 
-    /// Create marker file in repo to indicate encryption is used
-    pub fn create_encryption_marker(repo_path: &Path) -> Result<()> {
+    /// Create marker file in repo recording the KDF parameters used for this repo, so any
+    /// machine can reproduce the same key from the seed phrase (or passphrase).
+    ///
+    /// This is the legacy single-recipient mode: the mnemonic-derived key *is* the file
+    /// encryption key. Repos that need several independent seed phrases should use
+    /// `setup_multi_recipient` instead.
+    pub fn create_encryption_marker(repo_path: &Path, kdf: &KdfParams) -> Result<()> {
         let marker_path = Self::get_marker_file_path(repo_path);
-        fs::write(&marker_path, "This repository uses BIP39 seed phrase encryption.\nThe encryption key is stored in your home directory, NOT in this repo.\nYou will need your 12-word seed phrase to decrypt files on a new machine.")
+        let marker = EncryptionMarker {
+            notice: "This repository uses BIP39 seed phrase encryption.\nThe encryption key is stored in your home directory, NOT in this repo.\nYou will need your 12-word seed phrase to decrypt files on a new machine.".to_string(),
+            kdf: Some(kdf.clone()),
+            recipients: Vec::new(),
+        };
+        let content = serde_json::to_string_pretty(&marker)
+            .context("Failed to serialize encryption marker")?;
+        fs::write(&marker_path, content)
             .context("Failed to create encryption marker file")?;
         Ok(())
     }
 
+    /// Create the marker file for a fresh repo in multi-recipient mode: a random data
+    /// encryption key is generated and wrapped for a single initial recipient. Further
+    /// machines can unlock the same key from their own seed phrase via `add_recipient`,
+    /// without ever seeing this one. Returns the data encryption key to use for files.
+    pub fn setup_multi_recipient(repo_path: &Path, label: &str, mnemonic: &Mnemonic) -> Result<[u8; KEY_SIZE]> {
+        let mut data_key = [0u8; KEY_SIZE];
+        OsRng.fill_bytes(&mut data_key);
+
+        let recipient = Self::wrap_key_for_recipient(&data_key, label, mnemonic)?;
+
+        let marker_path = Self::get_marker_file_path(repo_path);
+        let marker = EncryptionMarker {
+            notice: "This repository uses BIP39 seed phrase encryption with multi-recipient key wrapping.\nEach machine's seed phrase unwraps the same shared file key independently.\nUse `add_recipient` to let another seed phrase unlock this repo.".to_string(),
+            kdf: None,
+            recipients: vec![recipient],
+        };
+        let content = serde_json::to_string_pretty(&marker)
+            .context("Failed to serialize encryption marker")?;
+        fs::write(&marker_path, content)
+            .context("Failed to create encryption marker file")?;
+
+        Ok(data_key)
+    }
+
+    /// Wrap an existing data encryption key for a new recipient's seed phrase, using
+    /// fresh KDF parameters (so each recipient gets their own salt), and append it to
+    /// the repo's marker file. `unlocking_mnemonic` must already unwrap the repo's key.
+    pub fn add_recipient(repo_path: &Path, unlocking_mnemonic: &Mnemonic, label: &str, new_mnemonic: &Mnemonic) -> Result<()> {
+        let data_key = Self::unwrap_key(repo_path, unlocking_mnemonic)
+            .context("Failed to unlock repository with the provided seed phrase")?;
+
+        let marker_path = Self::get_marker_file_path(repo_path);
+        let content = fs::read_to_string(&marker_path)
+            .context("Failed to read encryption marker file")?;
+        let mut marker: EncryptionMarker = serde_json::from_str(&content)
+            .context("Repository is not using multi-recipient key wrapping")?;
+
+        if marker.recipients.iter().any(|r| r.label == label) {
+            bail!("Recipient '{}' is already registered", label);
+        }
+
+        marker.recipients.push(Self::wrap_key_for_recipient(&data_key, label, new_mnemonic)?);
+
+        let content = serde_json::to_string_pretty(&marker)
+            .context("Failed to serialize encryption marker")?;
+        fs::write(&marker_path, content)
+            .context("Failed to update encryption marker file")?;
+        Ok(())
+    }
+
+    fn wrap_key_for_recipient(data_key: &[u8; KEY_SIZE], label: &str, mnemonic: &Mnemonic) -> Result<WrappedKey> {
+        let kdf = KdfParams::generate_argon2id();
+        let recipient_key = Self::derive_key_from_mnemonic(mnemonic, &kdf)?;
+        let wrapped = Self::encrypt_data(data_key, &recipient_key)
+            .context("Failed to wrap data key for recipient")?;
+        Ok(WrappedKey { label: label.to_string(), kdf, wrapped })
+    }
+
+    /// Unlock the repo's data encryption key with a mnemonic: tries the legacy
+    /// single-KDF marker first, then each registered recipient in turn, returning the
+    /// first key whose unwrap succeeds.
+    pub fn unwrap_key(repo_path: &Path, mnemonic: &Mnemonic) -> Result<[u8; KEY_SIZE]> {
+        let marker_path = Self::get_marker_file_path(repo_path);
+        let content = fs::read_to_string(&marker_path)
+            .context("Failed to read encryption marker file")?;
+
+        let marker: EncryptionMarker = match serde_json::from_str(&content) {
+            Ok(marker) => marker,
+            Err(_) => return Self::derive_key_from_mnemonic(mnemonic, &KdfParams::legacy_pbkdf2()),
+        };
+
+        if let Some(kdf) = &marker.kdf {
+            return Self::derive_key_from_mnemonic(mnemonic, kdf);
+        }
+
+        for recipient in &marker.recipients {
+            if let Ok(recipient_key) = Self::derive_key_from_mnemonic(mnemonic, &recipient.kdf) {
+                if let Ok(data_key) = Self::decrypt_data(&recipient.wrapped, &recipient_key) {
+                    if data_key.len() == KEY_SIZE {
+                        let mut key = [0u8; KEY_SIZE];
+                        key.copy_from_slice(&data_key);
+                        return Ok(key);
+                    }
+                }
+            }
+        }
+
+        bail!("Seed phrase does not match any registered recipient for this repository")
+    }
+
+    /// Load the KDF parameters recorded in the repo's marker file. Marker files written
+    /// before this migration carry only the plain-text notice; for those, fall back to
+    /// the original hardcoded PBKDF2 scheme so old repos keep decrypting. Only
+    /// meaningful for legacy single-recipient markers; multi-recipient repos should use
+    /// `unwrap_key` instead since each recipient has its own KDF parameters.
+    pub fn load_kdf_params(repo_path: &Path) -> Result<KdfParams> {
+        let marker_path = Self::get_marker_file_path(repo_path);
+        let content = fs::read_to_string(&marker_path)
+            .context("Failed to read encryption marker file")?;
+
+        match serde_json::from_str::<EncryptionMarker>(&content) {
+            Ok(marker) => marker.kdf.ok_or_else(|| anyhow::anyhow!("Repository uses multi-recipient key wrapping; use unwrap_key instead")),
+            Err(_) => Ok(KdfParams::legacy_pbkdf2()),
+        }
+    }
+
     /// Load encryption key from HOME directory
     pub fn load_key_from_home() -> Result<[u8; KEY_SIZE]> {
         let key_path = Self::get_key_file_path()?;
-        
+
         if !key_path.exists() {
             bail!("Encryption key not found in home directory. You need to enter your seed phrase.");
         }
 
         let encoded = fs::read_to_string(&key_path)
             .context("Failed to read encryption key file")?;
-        
+
         let decoded = base64::decode(encoded.trim())
             .context("Failed to decode encryption key")?;
-        
+
+        if decoded.len() != KEY_SIZE {
+            bail!("Invalid encryption key size");
+        }
+
+        let mut key = [0u8; KEY_SIZE];
+        key.copy_from_slice(&decoded);
+        Ok(key)
+    }
+
+    fn get_repo_key_file_path(repo_path: &Path) -> PathBuf {
+        repo_path.join(REPO_ENCRYPTION_KEY_FILE)
+    }
+
+    /// Save the encryption key scoped to this repo rather than the whole machine
+    /// (`dotfiles init` gitignores this file, so it stays local and never gets pushed).
+    pub fn save_key_to_repo(repo_path: &Path, key: &[u8; KEY_SIZE]) -> Result<()> {
+        let key_path = Self::get_repo_key_file_path(repo_path);
+        let encoded = base64::encode(key);
+        fs::write(&key_path, encoded).context("Failed to write encryption key to repository")?;
+        Ok(())
+    }
+
+    /// Load the repo-scoped key saved by `save_key_to_repo` (or `unlock`).
+    pub fn load_key_from_repo(repo_path: &Path) -> Result<[u8; KEY_SIZE]> {
+        let key_path = Self::get_repo_key_file_path(repo_path);
+
+        if !key_path.exists() {
+            bail!("Encryption key not found in repository. Run 'dotfiles unlock' with your seed phrase.");
+        }
+
+        let encoded = fs::read_to_string(&key_path).context("Failed to read encryption key file")?;
+
+        let decoded = base64::decode(encoded.trim()).context("Failed to decode encryption key")?;
+
         if decoded.len() != KEY_SIZE {
             bail!("Invalid encryption key size");
         }
@@ -102,85 +535,337 @@ impl FileEncryptor {
         Ok(key)
     }
 
-    /// Encrypt a file using the provided key
+    /// Encrypt a file using the provided key and the default algorithm (AES-256-GCM).
+    /// The plaintext is streamed through in fixed-size chunks so memory use stays
+    /// bounded regardless of file size; see `encrypt_file_with`.
     pub fn encrypt_file(source: &Path, dest: &Path, key: &[u8; KEY_SIZE]) -> Result<()> {
-        let content = fs::read(source).context("Failed to read source file")?;
-        let encrypted = Self::encrypt_data(&content, key)?;
-        
+        Self::encrypt_file_with(source, dest, key, EncryptionType::default())
+    }
+
+    /// Encrypt a file using the provided key and a chosen AEAD algorithm.
+    ///
+    /// The plaintext is split into `DEFAULT_CHUNK_SIZE` blocks and each is encrypted
+    /// independently under `nonce_prefix || u32_be(block_index)`, with the block index
+    /// and a final-block flag fed in as AAD. That binds every chunk to its position, so
+    /// reordering, dropping, or appending chunks fails AEAD verification on decrypt
+    /// instead of silently producing corrupted plaintext.
+    pub fn encrypt_file_with(source: &Path, dest: &Path, key: &[u8; KEY_SIZE], algo: EncryptionType) -> Result<()> {
+        let mut reader = BufReader::new(fs::File::open(source).context("Failed to read source file")?);
+
         if let Some(parent) = dest.parent() {
             fs::create_dir_all(parent)?;
         }
-        
-        fs::write(dest, encrypted).context("Failed to write encrypted file")?;
+        let mut writer = BufWriter::new(fs::File::create(dest).context("Failed to write encrypted file")?);
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        OsRng.fill_bytes(&mut nonce_prefix);
+
+        writer.write_all(&[FORMAT_MAGIC, FORMAT_VERSION_CHUNKED, algo.tag()])?;
+        writer.write_all(&(DEFAULT_CHUNK_SIZE as u32).to_be_bytes())?;
+        writer.write_all(&nonce_prefix)?;
+
+        let mut index: u32 = 0;
+        let mut buf = vec![0u8; DEFAULT_CHUNK_SIZE];
+        loop {
+            let n = read_chunk(&mut reader, &mut buf)?;
+            let is_final = n < DEFAULT_CHUNK_SIZE || reader.fill_buf()?.is_empty();
+
+            let nonce_bytes = chunk_nonce(&nonce_prefix, index);
+            let aad = chunk_aad(index, is_final);
+            let ciphertext = encrypt_chunk(algo, key, &nonce_bytes, &buf[..n], &aad)?;
+            writer.write_all(&ciphertext)?;
+
+            index += 1;
+            if is_final {
+                break;
+            }
+        }
+
+        writer.flush().context("Failed to flush encrypted file")?;
         Ok(())
     }
 
-    /// Decrypt a file using the provided key
+    /// Decrypt a file using the provided key, streaming chunk-by-chunk so memory use
+    /// stays bounded. Files written before the chunked format (format version 1) are
+    /// still read in full and decrypted in one shot for backward compatibility.
     pub fn decrypt_file(source: &Path, dest: &Path, key: &[u8; KEY_SIZE]) -> Result<()> {
-        let encrypted = fs::read(source).context("Failed to read encrypted file")?;
-        let decrypted = Self::decrypt_data(&encrypted, key)?;
-        
+        let mut reader = BufReader::new(fs::File::open(source).context("Failed to read encrypted file")?);
+
+        let mut header = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut header).context("Invalid encrypted file: missing header")?;
+
+        if header[0] != FORMAT_MAGIC {
+            bail_on_legacy_unauthenticated_format(source)?;
+        }
+
         if let Some(parent) = dest.parent() {
             fs::create_dir_all(parent)?;
         }
-        
-        fs::write(dest, decrypted).context("Failed to write decrypted file")?;
+
+        match header[1] {
+            FORMAT_VERSION => {
+                // Legacy non-chunked format: read the rest and decrypt in one shot.
+                let mut rest = Vec::new();
+                reader.read_to_end(&mut rest)?;
+                let mut full = header.to_vec();
+                full.extend_from_slice(&rest);
+                let plaintext = Self::decrypt_data(&full, key)?;
+                fs::write(dest, plaintext).context("Failed to write decrypted file")?;
+            }
+            FORMAT_VERSION_CHUNKED => {
+                let algo = EncryptionType::from_tag(header[2])?;
+
+                let mut chunk_size_bytes = [0u8; 4];
+                reader.read_exact(&mut chunk_size_bytes).context("Invalid encrypted file: missing chunk size")?;
+                let chunk_size = u32::from_be_bytes(chunk_size_bytes) as usize;
+
+                let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+                reader.read_exact(&mut nonce_prefix).context("Invalid encrypted file: missing nonce prefix")?;
+
+                let mut writer = BufWriter::new(fs::File::create(dest).context("Failed to write decrypted file")?);
+
+                let mut index: u32 = 0;
+                let mut buf = vec![0u8; chunk_size + AEAD_TAG_SIZE];
+                loop {
+                    let n = read_chunk(&mut reader, &mut buf)?;
+                    if n == 0 {
+                        bail!("Invalid encrypted file: missing expected final block");
+                    }
+                    let is_final = n < buf.len() || reader.fill_buf()?.is_empty();
+
+                    let nonce_bytes = chunk_nonce(&nonce_prefix, index);
+                    let aad = chunk_aad(index, is_final);
+                    let plaintext = decrypt_chunk(algo, key, &nonce_bytes, &buf[..n], &aad)
+                        .context("Chunk authentication failed (file may be truncated, reordered, or tampered with)")?;
+                    writer.write_all(&plaintext)?;
+
+                    index += 1;
+                    if is_final {
+                        break;
+                    }
+                }
+
+                writer.flush().context("Failed to flush decrypted file")?;
+            }
+            other => bail!("Unsupported encrypted file format version: {}", other),
+        }
+
         Ok(())
     }
 
-    /// Encrypt data using the provided key
+    /// Compute the plaintext length of a chunked-format encrypted file without
+    /// decrypting it, by reasoning about the fixed per-chunk ciphertext size instead.
+    /// Legacy whole-blob (format version 1) files are decrypted in full, since there's
+    /// no cheaper way to learn their length. Used by `mount` to answer `getattr`
+    /// without decrypting file contents.
+    pub fn plaintext_len(source: &Path, key: &[u8; KEY_SIZE]) -> Result<u64> {
+        let mut reader = BufReader::new(fs::File::open(source).context("Failed to read encrypted file")?);
+        let mut header = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut header).context("Invalid encrypted file: missing header")?;
+
+        if header[0] != FORMAT_MAGIC {
+            bail_on_legacy_unauthenticated_format(source)?;
+        }
+
+        match header[1] {
+            FORMAT_VERSION => {
+                let mut rest = Vec::new();
+                reader.read_to_end(&mut rest)?;
+                let mut full = header.to_vec();
+                full.extend_from_slice(&rest);
+                Ok(Self::decrypt_data(&full, key)?.len() as u64)
+            }
+            FORMAT_VERSION_CHUNKED => {
+                let (chunk_size, _, preamble_len) = Self::read_chunked_preamble(&mut reader, header[2])?;
+                let file_len = fs::metadata(source)?.len();
+                let ciphertext_total = file_len.saturating_sub(preamble_len as u64);
+                let full_chunk_ct_len = (chunk_size + AEAD_TAG_SIZE) as u64;
+                let num_full = ciphertext_total / full_chunk_ct_len;
+                let remainder = ciphertext_total % full_chunk_ct_len;
+
+                let len = if remainder > 0 {
+                    num_full * chunk_size as u64 + remainder - AEAD_TAG_SIZE as u64
+                } else {
+                    num_full * chunk_size as u64
+                };
+                Ok(len)
+            }
+            other => bail!("Unsupported encrypted file format version: {}", other),
+        }
+    }
+
+    /// Decrypt and return only the plaintext byte range `[offset, offset + len)`,
+    /// decrypting only the chunks that range touches instead of the whole file. Used
+    /// by `mount` to serve FUSE reads lazily. Legacy whole-blob files fall back to a
+    /// full decrypt, then slice.
+    pub fn read_plaintext_range(source: &Path, key: &[u8; KEY_SIZE], offset: u64, len: usize) -> Result<Vec<u8>> {
+        let mut reader = BufReader::new(fs::File::open(source).context("Failed to read encrypted file")?);
+        let mut header = [0u8; HEADER_SIZE];
+        reader.read_exact(&mut header).context("Invalid encrypted file: missing header")?;
+
+        if header[0] != FORMAT_MAGIC {
+            bail_on_legacy_unauthenticated_format(source)?;
+        }
+
+        match header[1] {
+            FORMAT_VERSION => {
+                let mut rest = Vec::new();
+                reader.read_to_end(&mut rest)?;
+                let mut full = header.to_vec();
+                full.extend_from_slice(&rest);
+                let plaintext = Self::decrypt_data(&full, key)?;
+                let start = (offset as usize).min(plaintext.len());
+                let end = (start + len).min(plaintext.len());
+                Ok(plaintext[start..end].to_vec())
+            }
+            FORMAT_VERSION_CHUNKED => {
+                let (chunk_size, algo, preamble_len) = Self::read_chunked_preamble(&mut reader, header[2])?;
+                let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+                reader.seek(std::io::SeekFrom::Start((preamble_len - NONCE_PREFIX_SIZE) as u64))?;
+                reader.read_exact(&mut nonce_prefix)?;
+
+                let file_len = fs::metadata(source)?.len();
+                let ciphertext_total = file_len.saturating_sub(preamble_len as u64);
+                let full_chunk_ct_len = (chunk_size + AEAD_TAG_SIZE) as u64;
+                let num_full = ciphertext_total / full_chunk_ct_len;
+                let remainder = ciphertext_total % full_chunk_ct_len;
+                let total_chunks = if remainder > 0 { num_full + 1 } else { num_full };
+
+                if len == 0 || total_chunks == 0 {
+                    return Ok(Vec::new());
+                }
+
+                let start_chunk = offset / chunk_size as u64;
+                let end_chunk = ((offset + len as u64).saturating_sub(1)) / chunk_size as u64;
+
+                let mut out = Vec::new();
+                for index in start_chunk..=end_chunk.min(total_chunks - 1) {
+                    let is_final = index == total_chunks - 1;
+                    let ct_len = if is_final && remainder > 0 { remainder } else { full_chunk_ct_len };
+
+                    reader.seek(std::io::SeekFrom::Start(preamble_len as u64 + index * full_chunk_ct_len))?;
+                    let mut buf = vec![0u8; ct_len as usize];
+                    reader.read_exact(&mut buf).context("Invalid encrypted file: truncated chunk")?;
+
+                    let nonce_bytes = chunk_nonce(&nonce_prefix, index as u32);
+                    let aad = chunk_aad(index as u32, is_final);
+                    let plaintext = decrypt_chunk(algo, key, &nonce_bytes, &buf, &aad)
+                        .context("Chunk authentication failed (file may be truncated, reordered, or tampered with)")?;
+                    out.extend_from_slice(&plaintext);
+                }
+
+                let chunk_range_start = start_chunk * chunk_size as u64;
+                let skip = (offset - chunk_range_start) as usize;
+                let end = (skip + len).min(out.len());
+                Ok(out[skip.min(out.len())..end].to_vec())
+            }
+            other => bail!("Unsupported encrypted file format version: {}", other),
+        }
+    }
+
+    /// Read the fixed preamble of a chunked-format file (chunk size, nonce prefix)
+    /// following the 3-byte header, and return `(chunk_size, algorithm, preamble_len)`.
+    /// `algo_tag` is the header's third byte, already consumed by the caller.
+    fn read_chunked_preamble(reader: &mut BufReader<fs::File>, algo_tag: u8) -> Result<(usize, EncryptionType, usize)> {
+        let algo = EncryptionType::from_tag(algo_tag)?;
+
+        let mut chunk_size_bytes = [0u8; 4];
+        reader.read_exact(&mut chunk_size_bytes).context("Invalid encrypted file: missing chunk size")?;
+        let chunk_size = u32::from_be_bytes(chunk_size_bytes) as usize;
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        reader.read_exact(&mut nonce_prefix).context("Invalid encrypted file: missing nonce prefix")?;
+
+        let preamble_len = HEADER_SIZE + 4 + NONCE_PREFIX_SIZE;
+        Ok((chunk_size, algo, preamble_len))
+    }
+
+    /// Encrypt data using the provided key and the default algorithm (AES-256-GCM)
     pub fn encrypt_data(data: &[u8], key: &[u8; KEY_SIZE]) -> Result<Vec<u8>> {
-        let cipher = Aes256Gcm::new_from_slice(key)
-            .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
-        
+        Self::encrypt_data_with(data, key, EncryptionType::default())
+    }
+
+    /// Encrypt data, writing a self-describing header (magic, format version, algorithm
+    /// tag) ahead of the nonce and ciphertext so future format changes stay decryptable.
+    pub fn encrypt_data_with(data: &[u8], key: &[u8; KEY_SIZE], algo: EncryptionType) -> Result<Vec<u8>> {
         let mut nonce_bytes = [0u8; NONCE_SIZE];
         OsRng.fill_bytes(&mut nonce_bytes);
-        let nonce = Nonce::from_slice(&nonce_bytes);
-        
-        let ciphertext = cipher
-            .encrypt(nonce, data)
-            .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
-        
-        let mut result = Vec::new();
+        let nonce = GenericArray::from_slice(&nonce_bytes);
+
+        let ciphertext = match algo {
+            EncryptionType::AesGcm => {
+                let cipher = Aes256Gcm::new_from_slice(key)
+                    .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+                cipher.encrypt(nonce, data)
+                    .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?
+            }
+            EncryptionType::ChaCha20Poly1305 => {
+                let cipher = ChaCha20Poly1305::new_from_slice(key)
+                    .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+                cipher.encrypt(nonce, data)
+                    .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?
+            }
+        };
+
+        let mut result = Vec::with_capacity(HEADER_SIZE + NONCE_SIZE + ciphertext.len());
+        result.push(FORMAT_MAGIC);
+        result.push(FORMAT_VERSION);
+        result.push(algo.tag());
         result.extend_from_slice(&nonce_bytes);
         result.extend_from_slice(&ciphertext);
-        
+
         Ok(result)
     }
 
-    /// Decrypt data using the provided key
+    /// Decrypt data, reading the header to determine which algorithm to dispatch to
     pub fn decrypt_data(data: &[u8], key: &[u8; KEY_SIZE]) -> Result<Vec<u8>> {
-        if data.len() < NONCE_SIZE {
+        if data.len() < HEADER_SIZE + NONCE_SIZE {
             bail!("Invalid encrypted data: too short");
         }
 
-        let cipher = Aes256Gcm::new_from_slice(key)
-            .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
-        
-        let nonce = Nonce::from_slice(&data[..NONCE_SIZE]);
-        let ciphertext = &data[NONCE_SIZE..];
-        
-        let plaintext = cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?;
-        
+        if data[0] != FORMAT_MAGIC {
+            bail!("Invalid encrypted data: unrecognized header magic");
+        }
+        if data[1] != FORMAT_VERSION {
+            bail!("Unsupported encrypted file format version: {}", data[1]);
+        }
+
+        let algo = EncryptionType::from_tag(data[2])?;
+        let nonce = GenericArray::from_slice(&data[HEADER_SIZE..HEADER_SIZE + NONCE_SIZE]);
+        let ciphertext = &data[HEADER_SIZE + NONCE_SIZE..];
+
+        let plaintext = match algo {
+            EncryptionType::AesGcm => {
+                let cipher = Aes256Gcm::new_from_slice(key)
+                    .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+                cipher.decrypt(nonce, ciphertext)
+                    .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?
+            }
+            EncryptionType::ChaCha20Poly1305 => {
+                let cipher = ChaCha20Poly1305::new_from_slice(key)
+                    .map_err(|e| anyhow::anyhow!("Failed to create cipher: {}", e))?;
+                cipher.decrypt(nonce, ciphertext)
+                    .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?
+            }
+        };
+
         Ok(plaintext)
     }
 
     /// Display the seed phrase to the user with prominent warnings
     pub fn display_seed_phrase(mnemonic: &Mnemonic) {
         use colored::Colorize;
-        
+
         println!();
-        println!("{}", "‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê".yellow().bold());
-        println!("{}", "                  üîê ENCRYPTION SEED PHRASE                   ".yellow().bold());
-        println!("{}", "‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê".yellow().bold());
+        println!("{}", "════════════════════════════════════════════════════════════".yellow().bold());
+        println!("{}", "                  🔐 ENCRYPTION SEED PHRASE                   ".yellow().bold());
+        println!("{}", "════════════════════════════════════════════════════════════".yellow().bold());
         println!();
-        println!("{}", "‚ö†Ô∏è  CRITICAL: SAVE THIS SEED PHRASE NOW! ‚ö†Ô∏è".red().bold());
+        println!("{}", "⚠️  CRITICAL: SAVE THIS SEED PHRASE NOW! ⚠️".red().bold());
         println!();
         println!("   {}", "This is your 12-word BIP39 seed phrase:".bold());
         println!();
-        
+
         let words: Vec<&str> = mnemonic.word_iter().collect();
         for (i, word) in words.iter().enumerate() {
             print!("   {:2}. {:12}", i + 1, word.green().bold());
@@ -190,38 +875,147 @@ impl FileEncryptor {
         }
         println!();
         println!();
-        println!("{}", "‚ö†Ô∏è  IMPORTANT SECURITY NOTICE:".yellow().bold());
-        println!("   ‚Ä¢ {}", "You will NOT see this seed phrase again".bold());
-        println!("   ‚Ä¢ {}", "Write it down on paper (NOT digitally)".bold());
-        println!("   ‚Ä¢ {}", "Keep it in a safe place".bold());
-        println!("   ‚Ä¢ {}", "You need this to decrypt files on new machines".bold());
-        println!("   ‚Ä¢ {}", "Anyone with this phrase can decrypt your files".bold());
+        println!("{}", "⚠️  IMPORTANT SECURITY NOTICE:".yellow().bold());
+        println!("   • {}", "You will NOT see this seed phrase again".bold());
+        println!("   • {}", "Write it down on paper (NOT digitally)".bold());
+        println!("   • {}", "Keep it in a safe place".bold());
+        println!("   • {}", "You need this to decrypt files on new machines".bold());
+        println!("   • {}", "Anyone with this phrase can decrypt your files".bold());
         println!();
-        println!("{}", "‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê‚ïê".yellow().bold());
+        println!("{}", "════════════════════════════════════════════════════════════".yellow().bold());
         println!();
     }
 
     /// Prompt user to enter their seed phrase for decryption
     pub fn prompt_for_seed_phrase() -> Result<Mnemonic> {
         use colored::Colorize;
-        
+
         println!();
-        println!("{}", "üîê Enter your 12-word seed phrase to decrypt files:".bold());
+        println!("{}", "🔐 Enter your 12-word seed phrase to decrypt files:".bold());
         println!("   (Enter all 12 words separated by spaces)");
         println!();
         print!("   Seed phrase: ");
-        
+
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)
             .context("Failed to read seed phrase")?;
-        
+
         let mnemonic = Mnemonic::parse_in(Language::English, input.trim())
             .map_err(|e| anyhow::anyhow!("Invalid seed phrase: {}", e))?;
-        
+
         if mnemonic.word_count() != 12 {
             bail!("Seed phrase must be exactly 12 words");
         }
-        
+
         Ok(mnemonic)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_path(label: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("dotfiles_encryption_test_{label}_{}", uuid::Uuid::new_v4()))
+    }
+
+    fn test_key() -> [u8; KEY_SIZE] {
+        let params = KdfParams::generate_argon2id();
+        FileEncryptor::derive_key_from_passphrase("correct horse battery staple", &params).unwrap()
+    }
+
+    #[test]
+    fn encrypt_data_round_trips() {
+        let key = test_key();
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+
+        let ciphertext = FileEncryptor::encrypt_data(plaintext, &key).unwrap();
+        let decrypted = FileEncryptor::decrypt_data(&ciphertext, &key).unwrap();
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn encrypt_data_detects_tampering() {
+        let key = test_key();
+        let mut ciphertext = FileEncryptor::encrypt_data(b"sensitive contents", &key).unwrap();
+
+        let last = ciphertext.len() - 1;
+        ciphertext[last] ^= 0xFF;
+
+        assert!(FileEncryptor::decrypt_data(&ciphertext, &key).is_err());
+    }
+
+    #[test]
+    fn encrypt_data_rejects_wrong_key() {
+        let key = test_key();
+        let other_key = test_key();
+        let ciphertext = FileEncryptor::encrypt_data(b"sensitive contents", &key).unwrap();
+
+        assert!(FileEncryptor::decrypt_data(&ciphertext, &other_key).is_err());
+    }
+
+    #[test]
+    fn encrypt_file_round_trips_across_multiple_chunks() {
+        let key = test_key();
+        let source = scratch_path("plain");
+        let encrypted = scratch_path("enc");
+        let decrypted = scratch_path("dec");
+
+        // Larger than DEFAULT_CHUNK_SIZE so the round trip exercises more than one chunk.
+        let plaintext = vec![0x42u8; DEFAULT_CHUNK_SIZE * 2 + 17];
+        fs::write(&source, &plaintext).unwrap();
+
+        FileEncryptor::encrypt_file(&source, &encrypted, &key).unwrap();
+        FileEncryptor::decrypt_file(&encrypted, &decrypted, &key).unwrap();
+
+        let result = fs::read(&decrypted).unwrap();
+        assert_eq!(result, plaintext);
+
+        let _ = fs::remove_file(&source);
+        let _ = fs::remove_file(&encrypted);
+        let _ = fs::remove_file(&decrypted);
+    }
+
+    #[test]
+    fn encrypt_file_detects_tampered_chunk() {
+        let key = test_key();
+        let source = scratch_path("plain");
+        let encrypted = scratch_path("enc");
+        let decrypted = scratch_path("dec");
+
+        fs::write(&source, b"hello from a tampered chunk test").unwrap();
+        FileEncryptor::encrypt_file(&source, &encrypted, &key).unwrap();
+
+        let mut bytes = fs::read(&encrypted).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        fs::write(&encrypted, &bytes).unwrap();
+
+        assert!(FileEncryptor::decrypt_file(&encrypted, &decrypted, &key).is_err());
+
+        let _ = fs::remove_file(&source);
+        let _ = fs::remove_file(&encrypted);
+        let _ = fs::remove_file(&decrypted);
+    }
+
+    #[test]
+    fn decrypt_file_rejects_missing_header() {
+        let key = test_key();
+        let source = scratch_path("nonsense");
+        let decrypted = scratch_path("dec");
+        fs::write(&source, b"not an encrypted file at all").unwrap();
+
+        assert!(FileEncryptor::decrypt_file(&source, &decrypted, &key).is_err());
+
+        let _ = fs::remove_file(&source);
+    }
+
+    #[test]
+    fn derive_key_from_passphrase_is_deterministic_for_same_params() {
+        let params = KdfParams::generate_argon2id();
+        let key1 = FileEncryptor::derive_key_from_passphrase("a passphrase", &params).unwrap();
+        let key2 = FileEncryptor::derive_key_from_passphrase("a passphrase", &params).unwrap();
+        assert_eq!(key1, key2);
+    }
+}
@@ -2,81 +2,604 @@ use anyhow::{bail, Context, Result};
 use std::path::Path;
 use std::process::Command;
 
+/// Resolves the HTTPS credential `libgit2` falls back to when ssh-agent and
+/// `~/.ssh/id_*` keys don't satisfy a remote's auth challenge: `git_token` from the
+/// repo's local config, then the `DOTFILES_GIT_TOKEN` env var.
+fn resolve_git_token(repo_path: &Path) -> Option<String> {
+    let configured = crate::config::ConfigManager::new(repo_path.to_path_buf())
+        .load_git_token()
+        .ok()
+        .flatten();
+
+    configured.or_else(|| {
+        std::env::var("DOTFILES_GIT_TOKEN")
+            .ok()
+            .filter(|t| !t.is_empty())
+    })
+}
+
+/// A progress event surfaced during `push`/`pull_rebase`, modeled on libgit2's own
+/// transfer-progress callback shapes so the same enum fits both backends: `git2`
+/// reports these directly from its callbacks, while the shell backend parses them out
+/// of `git --progress`'s stderr.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushProgress {
+    /// A ref was updated to a new target; `old_oid`/`new_oid` are hex object IDs.
+    UpdateTips {
+        refname: String,
+        old_oid: String,
+        new_oid: String,
+    },
+    /// Objects transferred so far (used during `pull_rebase`'s fetch).
+    Transfer {
+        objects: usize,
+        total_objects: usize,
+    },
+    /// Bytes of the pack written to the remote so far (used during `push`).
+    PushTransfer {
+        current: usize,
+        total: usize,
+        bytes: usize,
+    },
+}
+
+/// Callback invoked with each `PushProgress` event as a push/pull progresses. A plain
+/// `&mut dyn FnMut` rather than a generic so `GitBackend` stays object-safe.
+pub type ProgressCallback<'a> = dyn FnMut(PushProgress) + 'a;
+
+/// Which `GitBackend` implementation to use for a repo. Selected from config so users
+/// without authenticated in-process credentials set up can fall back to the `git`
+/// binary on PATH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GitBackendKind {
+    /// Shells out to the `git` binary. Slower (a process spawn per call) but works
+    /// anywhere `git` is installed and already has credentials configured.
+    #[default]
+    Shell,
+    /// Talks to the repository in-process via `git2` (libgit2). No subprocess spawn
+    /// and typed errors instead of parsed stderr, at the cost of managing credentials
+    /// itself.
+    Libgit2,
+}
+
+impl GitBackendKind {
+    /// Parses the `git_backend` config field (`"shell"` or `"libgit2"`). Unknown
+    /// values fall back to `None` so callers can default to `Shell` rather than error.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "shell" => Some(GitBackendKind::Shell),
+            "libgit2" => Some(GitBackendKind::Libgit2),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GitBackendKind::Shell => "shell",
+            GitBackendKind::Libgit2 => "libgit2",
+        }
+    }
+}
+
+/// The operations `GitRepo` needs from a git implementation. Extracted so the CLI can
+/// run on top of either a shelled-out `git` binary or an in-process `git2` backend
+/// without the rest of the codebase caring which.
+pub trait GitBackend {
+    fn is_repo(&self) -> bool;
+    fn init(&self) -> Result<()>;
+    fn has_changes(&self) -> Result<bool>;
+    fn is_in_merge(&self) -> Result<bool>;
+    fn is_in_rebase(&self) -> Result<bool>;
+    fn has_conflicts(&self) -> Result<bool>;
+    fn get_conflicted_files(&self) -> Result<Vec<String>>;
+    fn rebase_continue(&self) -> Result<()>;
+    /// Abandons an in-progress rebase, restoring the working tree and index to their
+    /// state before `sync`/`pull_rebase` started it.
+    fn rebase_abort(&self) -> Result<()>;
+    fn add_all(&self) -> Result<()>;
+    fn commit(&self, message: &str) -> Result<()>;
+    fn stash(&self, message: &str) -> Result<bool>;
+    fn stash_pop(&self) -> Result<bool>;
+    fn get_stash_list(&self) -> Result<Vec<String>>;
+    fn pull(&self, remote: &str, branch: &str) -> Result<()>;
+    fn pull_rebase(&self, remote: &str, branch: &str) -> Result<()>;
+    /// Same as `pull_rebase`, but reports `PushProgress::Transfer`/`UpdateTips` events
+    /// as the fetch progresses, so a large initial pull doesn't look frozen.
+    fn pull_rebase_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()>;
+    fn push(&self, remote: &str, branch: &str) -> Result<()>;
+    /// Same as `push`, but reports `PushProgress::PushTransfer`/`UpdateTips` events as
+    /// the push progresses.
+    fn push_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()>;
+    fn push_set_upstream(&self, remote: &str, branch: &str) -> Result<()>;
+    /// Same as `push_set_upstream`, with progress reporting.
+    fn push_set_upstream_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()>;
+    fn has_remote(&self) -> Result<bool>;
+    /// Registers `url` as a new remote named `name`, e.g. the `origin` `push`/`pull`
+    /// offer to add automatically from `DotfilesConfig.remote` when none is set up yet.
+    fn add_remote(&self, name: &str, url: &str) -> Result<()>;
+    fn remote_has_commits(&self, remote: &str, branch: &str) -> Result<bool>;
+    fn get_current_branch(&self) -> Result<String>;
+    /// Reads `path`'s content at a given conflict `stage` out of the index (1 =
+    /// common ancestor/base, 2 = ours, 3 = theirs), as used to recover the three
+    /// sides of a merge conflict. Errors (rather than returning empty) when `path`
+    /// has no entry at `stage` — the add/add case where the base is missing.
+    fn get_file_version(&self, path: &str, stage: u8) -> Result<Vec<u8>>;
+    /// Packages the full history reachable from `branch` into a single bundle file,
+    /// for offline transfer to an air-gapped or new machine.
+    fn create_bundle(&self, dest: &Path, branch: &str) -> Result<()>;
+    /// Verifies `bundle` and merges it into `branch`, treating the bundle file as an
+    /// ad hoc remote the same way `pull_rebase` treats a named one: fetch, then
+    /// fast-forward if possible.
+    fn import_bundle(&self, bundle: &Path, branch: &str) -> Result<()>;
+}
+
+/// Facade over a pluggable `GitBackend`. Existing callers keep using `GitRepo::new`
+/// (which picks the shell backend) unchanged; `GitRepo::with_backend` lets config
+/// select `git2` instead.
 pub struct GitRepo {
-    repo_path: Box<Path>,
+    backend: Box<dyn GitBackend>,
 }
 
 impl GitRepo {
     pub fn new(path: &Path) -> Self {
+        Self::with_backend(path, GitBackendKind::Shell)
+    }
+
+    pub fn with_backend(path: &Path, kind: GitBackendKind) -> Self {
+        Self::with_backend_and_hardening(path, kind, true)
+    }
+
+    /// Like `with_backend`, but lets callers pass the resolved `disable_git_hardening`
+    /// config flag. `git2` itself never invokes a repo-local `.git/config`'s
+    /// `core.fsmonitor`/`core.sshCommand`/hooks as a subprocess, but its bundle support
+    /// does shell out to `git` (see `Git2Backend::create_bundle`/`import_bundle`), so
+    /// this flag reaches both backends.
+    pub fn with_backend_and_hardening(path: &Path, kind: GitBackendKind, harden: bool) -> Self {
+        let backend: Box<dyn GitBackend> = match kind {
+            GitBackendKind::Shell => Box::new(ShellGitBackend::with_hardening(path, harden)),
+            GitBackendKind::Libgit2 => Box::new(Git2Backend::new(path, harden)),
+        };
+        Self { backend }
+    }
+
+    pub fn is_repo(&self) -> bool {
+        self.backend.is_repo()
+    }
+
+    pub fn init(&self) -> Result<()> {
+        self.backend.init()
+    }
+
+    pub fn has_changes(&self) -> Result<bool> {
+        self.backend.has_changes()
+    }
+
+    pub fn is_dirty(&self) -> Result<bool> {
+        self.has_changes()
+    }
+
+    pub fn is_in_merge(&self) -> Result<bool> {
+        self.backend.is_in_merge()
+    }
+
+    pub fn is_in_rebase(&self) -> Result<bool> {
+        self.backend.is_in_rebase()
+    }
+
+    pub fn has_conflicts(&self) -> Result<bool> {
+        self.backend.has_conflicts()
+    }
+
+    pub fn get_conflicted_files(&self) -> Result<Vec<String>> {
+        self.backend.get_conflicted_files()
+    }
+
+    pub fn rebase_continue(&self) -> Result<()> {
+        self.backend.rebase_continue()
+    }
+
+    pub fn rebase_abort(&self) -> Result<()> {
+        self.backend.rebase_abort()
+    }
+
+    pub fn add_all(&self) -> Result<()> {
+        self.backend.add_all()
+    }
+
+    pub fn commit(&self, message: &str) -> Result<()> {
+        self.backend.commit(message)
+    }
+
+    pub fn stash(&self, message: &str) -> Result<bool> {
+        self.backend.stash(message)
+    }
+
+    pub fn stash_pop(&self) -> Result<bool> {
+        self.backend.stash_pop()
+    }
+
+    pub fn get_stash_list(&self) -> Result<Vec<String>> {
+        self.backend.get_stash_list()
+    }
+
+    pub fn pull(&self, remote: &str, branch: &str) -> Result<()> {
+        self.backend.pull(remote, branch)
+    }
+
+    pub fn pull_rebase(&self, remote: &str, branch: &str) -> Result<()> {
+        self.backend.pull_rebase(remote, branch)
+    }
+
+    pub fn pull_rebase_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.backend
+            .pull_rebase_with_progress(remote, branch, on_progress)
+    }
+
+    pub fn push(&self, remote: &str, branch: &str) -> Result<()> {
+        self.backend.push(remote, branch)
+    }
+
+    pub fn push_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.backend.push_with_progress(remote, branch, on_progress)
+    }
+
+    pub fn push_set_upstream(&self, remote: &str, branch: &str) -> Result<()> {
+        self.backend.push_set_upstream(remote, branch)
+    }
+
+    pub fn push_set_upstream_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.backend
+            .push_set_upstream_with_progress(remote, branch, on_progress)
+    }
+
+    pub fn has_remote(&self) -> Result<bool> {
+        self.backend.has_remote()
+    }
+
+    pub fn add_remote(&self, name: &str, url: &str) -> Result<()> {
+        self.backend.add_remote(name, url)
+    }
+
+    pub fn remote_has_commits(&self, remote: &str, branch: &str) -> Result<bool> {
+        self.backend.remote_has_commits(remote, branch)
+    }
+
+    pub fn get_current_branch(&self) -> Result<String> {
+        self.backend.get_current_branch()
+    }
+
+    pub fn get_file_version(&self, path: &str, stage: u8) -> Result<Vec<u8>> {
+        self.backend.get_file_version(path, stage)
+    }
+
+    pub fn create_bundle(&self, dest: &Path, branch: &str) -> Result<()> {
+        self.backend.create_bundle(dest, branch)
+    }
+
+    pub fn import_bundle(&self, bundle: &Path, branch: &str) -> Result<()> {
+        self.backend.import_bundle(bundle, branch)
+    }
+}
+
+/// No-op hooks path for the current platform, used to neutralize `.git/hooks` when
+/// hardening is enabled.
+#[cfg(windows)]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(windows))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// Builds a `git` invocation rooted at `repo_path`, layering on hardening flags ahead
+/// of `args` and scrubbing `GIT_*` environment variables that could redirect execution
+/// (custom `GIT_SSH`/`GIT_SSH_COMMAND`, alternate `GIT_DIR`/`GIT_WORK_TREE`, etc.)
+/// unless `harden` is false. Shared by `ShellGitBackend::command` and `Git2Backend`'s
+/// bundle operations — `git2` has no bundle API, so those two calls have to shell out
+/// to `git` the same as the shell backend does, and need the same protection against a
+/// cloned-from-untrusted repo's own `.git/config`.
+fn hardened_command(repo_path: &Path, args: &[&str], harden: bool) -> Command {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_path);
+
+    if harden {
+        for (key, _) in std::env::vars() {
+            if key.starts_with("GIT_") {
+                cmd.env_remove(key);
+            }
+        }
+        cmd.env("GIT_OPTIONAL_LOCKS", "0");
+        cmd.args(["-c", "core.fsmonitor=false"]);
+        cmd.args(["-c", "core.sshCommand=ssh"]);
+        cmd.args(["-c", &format!("core.hooksPath={NULL_DEVICE}")]);
+    }
+
+    cmd.args(args);
+    cmd
+}
+
+/// Shells out to the `git` binary for most operations. A cloned-from-untrusted repo's
+/// own `.git/config` runs in the context of every invocation here, so by default every
+/// call is hardened against `core.fsmonitor`, `core.sshCommand`, and hooks being used
+/// to execute arbitrary programs (the class of issue Starship had to patch when
+/// reading git state). `disable_git_hardening` in config opts back out for users who
+/// intentionally rely on fsmonitor. Conflict/index reads (`has_conflicts`,
+/// `get_conflicted_files`, `get_file_version`) go through `gix` (gitoxide) directly
+/// against the in-memory index instead, since those are read so routinely by the
+/// conflict-resolution flow that a subprocess-and-parse round trip per stage isn't
+/// worth it, and the index structure cleanly distinguishes "no such stage" from a
+/// binary blob in a way scraping `git`'s porcelain output doesn't.
+struct ShellGitBackend {
+    repo_path: Box<Path>,
+    harden: bool,
+}
+
+impl ShellGitBackend {
+    fn new(path: &Path) -> Self {
+        Self::with_hardening(path, true)
+    }
+
+    fn with_hardening(path: &Path, harden: bool) -> Self {
         Self {
             repo_path: path.into(),
+            harden,
         }
     }
 
-    pub fn is_repo(&self) -> bool {
+    /// Opens the repository with `gix`'s reduced-trust permissions, the same posture
+    /// hardening gives the shell path: a cloned-from-untrusted repo's own config isn't
+    /// trusted for things like executable hook paths.
+    fn gix_repo(&self) -> Result<gix::Repository> {
+        gix::open_opts(&self.repo_path, gix::open::Options::isolated())
+            .context("Failed to open git repository via gitoxide")
+    }
+
+    /// Every conflicted (non-`Unconflicted`) index entry, as `(repo-relative path,
+    /// stage, blob id)`. A path with an entry at stage 2 and 3 but not 1 is an
+    /// add/add conflict — `get_file_version` already treats a missing stage as an
+    /// error rather than empty content, so that distinction falls out for free.
+    fn gix_conflicted_entries(&self) -> Result<Vec<(String, gix::index::entry::Stage, gix::ObjectId)>> {
+        let repo = self.gix_repo()?;
+        let index = repo.index_or_empty().context("Failed to read git index")?;
+
+        let mut entries = Vec::new();
+        for entry in index.entries() {
+            if entry.stage() != gix::index::entry::Stage::Unconflicted {
+                let path = entry.path(&index).to_string();
+                entries.push((path, entry.stage(), entry.id));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Builds a `git` invocation, layering on hardening flags ahead of `args` and
+    /// scrubbing `GIT_*` environment variables that could redirect execution (custom
+    /// `GIT_SSH`/`GIT_SSH_COMMAND`, alternate `GIT_DIR`/`GIT_WORK_TREE`, etc.) unless
+    /// hardening has been explicitly disabled.
+    fn command(&self, args: &[&str]) -> Command {
+        hardened_command(&self.repo_path, args, self.harden)
+    }
+
+    fn run_command(&self, args: &[&str]) -> Result<()> {
+        let status = self
+            .command(args)
+            .status()
+            .context(format!("Failed to execute git {:?}", args))?;
+
+        if !status.success() {
+            bail!("Git command failed: {:?}", args);
+        }
+
+        Ok(())
+    }
+
+    /// Like `run_command`, but streams `git`'s stderr (where `--progress` writes its
+    /// updates) line by line, parsing each one into a `PushProgress` event. `git`
+    /// overwrites its progress line with a carriage return rather than a newline, so
+    /// both `\n` and `\r` are treated as line terminators.
+    fn run_command_with_progress(
+        &self,
+        args: &[&str],
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        use std::io::Read;
+        use std::process::Stdio;
+
+        let mut child = self
+            .command(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(format!("Failed to execute git {:?}", args))?;
+
+        let mut stderr = child.stderr.take().expect("stderr was piped");
+        let mut captured = String::new();
+        let mut current_line = Vec::new();
+        let mut byte = [0u8; 1];
+
+        while let Ok(n) = stderr.read(&mut byte) {
+            if n == 0 {
+                break;
+            }
+
+            if byte[0] == b'\n' || byte[0] == b'\r' {
+                if !current_line.is_empty() {
+                    let line = String::from_utf8_lossy(&current_line).into_owned();
+                    if let Some(progress) = parse_shell_progress_line(&line) {
+                        on_progress(progress);
+                    }
+                    captured.push_str(&line);
+                    captured.push('\n');
+                    current_line.clear();
+                }
+            } else {
+                current_line.push(byte[0]);
+            }
+        }
+
+        let status = child.wait().context("Failed to wait on git process")?;
+        if !status.success() {
+            bail!("Git command failed: {:?}: {}", args, captured.trim());
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses one line of `git --progress` stderr output into a `PushProgress` event, e.g.
+/// `"Receiving objects:  42% (21/50), 1.20 MiB | 598.00 KiB/s"` or a ref update summary
+/// like `"   1a2b3c4..5d6e7f8  main -> main"`. Best-effort: this is text meant for a
+/// terminal, not a stable machine format, so an unrecognized line is just ignored
+/// rather than treated as an error.
+fn parse_shell_progress_line(line: &str) -> Option<PushProgress> {
+    let line = line.trim();
+
+    if let Some(rest) = line
+        .strip_prefix("Writing objects:")
+        .or_else(|| line.strip_prefix("Compressing objects:"))
+    {
+        let (current, total, bytes) = parse_object_progress(rest)?;
+        return Some(PushProgress::PushTransfer {
+            current,
+            total,
+            bytes,
+        });
+    }
+
+    if let Some(rest) = line
+        .strip_prefix("Receiving objects:")
+        .or_else(|| line.strip_prefix("Resolving deltas:"))
+    {
+        let (current, total, _) = parse_object_progress(rest)?;
+        return Some(PushProgress::Transfer {
+            objects: current,
+            total_objects: total,
+        });
+    }
+
+    let (oids, refs) = line.split_once("  ")?;
+    let (old_oid, new_oid) = oids.trim().split_once("..")?;
+    if !is_hex_oid(old_oid) || !is_hex_oid(new_oid) {
+        return None;
+    }
+    let refname = refs.split_whitespace().last()?.to_string();
+    Some(PushProgress::UpdateTips {
+        refname,
+        old_oid: old_oid.to_string(),
+        new_oid: new_oid.to_string(),
+    })
+}
+
+/// Parses the `(current/total), <size> <unit> | ...` portion of a progress line into
+/// `(current, total, bytes)`. `bytes` is `0` when the line has no size component (e.g.
+/// "Counting objects: 100% (23/23), done.").
+fn parse_object_progress(rest: &str) -> Option<(usize, usize, usize)> {
+    let paren_start = rest.find('(')?;
+    let paren_end = rest.find(')')?;
+    let (current_str, total_str) = rest[paren_start + 1..paren_end].split_once('/')?;
+    let current: usize = current_str.trim().parse().ok()?;
+    let total: usize = total_str.trim().parse().ok()?;
+
+    let bytes = rest[paren_end + 1..]
+        .split(',')
+        .find_map(|part| parse_size_to_bytes(part.trim()))
+        .unwrap_or(0);
+
+    Some((current, total, bytes))
+}
+
+fn parse_size_to_bytes(part: &str) -> Option<usize> {
+    let mut tokens = part.split('|').next()?.trim().split_whitespace();
+    let number: f64 = tokens.next()?.parse().ok()?;
+    let multiplier = match tokens.next()? {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as usize)
+}
+
+fn is_hex_oid(s: &str) -> bool {
+    s.len() >= 4 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl GitBackend for ShellGitBackend {
+    fn is_repo(&self) -> bool {
         self.repo_path.join(".git").exists()
     }
 
-    pub fn init(&self) -> Result<()> {
+    fn init(&self) -> Result<()> {
         self.run_command(&["init"])?;
         Ok(())
     }
 
-    pub fn has_changes(&self) -> Result<bool> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["status", "--porcelain"])
+    fn has_changes(&self) -> Result<bool> {
+        let output = self
+            .command(&["status", "--porcelain"])
             .output()
             .context("Failed to check git status")?;
 
         Ok(!output.stdout.is_empty())
     }
 
-    pub fn is_dirty(&self) -> Result<bool> {
-        self.has_changes()
-    }
-
-    pub fn is_in_merge(&self) -> Result<bool> {
+    fn is_in_merge(&self) -> Result<bool> {
         let merge_head = self.repo_path.join(".git/MERGE_HEAD");
         Ok(merge_head.exists())
     }
 
-    pub fn is_in_rebase(&self) -> Result<bool> {
+    fn is_in_rebase(&self) -> Result<bool> {
         let rebase_merge = self.repo_path.join(".git/rebase-merge");
         let rebase_apply = self.repo_path.join(".git/rebase-apply");
         Ok(rebase_merge.exists() || rebase_apply.exists())
     }
 
-    pub fn has_conflicts(&self) -> Result<bool> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["diff", "--name-only", "--diff-filter=U"])
-            .output()
-            .context("Failed to check for conflicts")?;
-
-        Ok(!output.stdout.is_empty())
+    fn has_conflicts(&self) -> Result<bool> {
+        Ok(!self.gix_conflicted_entries()?.is_empty())
     }
 
-    pub fn get_conflicted_files(&self) -> Result<Vec<String>> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["diff", "--name-only", "--diff-filter=U"])
-            .output()
-            .context("Failed to get conflicted files")?;
-
-        let files = String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .map(|s| s.to_string())
+    fn get_conflicted_files(&self) -> Result<Vec<String>> {
+        let mut files: Vec<String> = self
+            .gix_conflicted_entries()?
+            .into_iter()
+            .map(|(path, _, _)| path)
             .collect();
+        files.sort();
+        files.dedup();
 
         Ok(files)
     }
 
-    pub fn rebase_continue(&self) -> Result<()> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["rebase", "--continue"])
-            .output()?;
+    fn rebase_continue(&self) -> Result<()> {
+        let output = self.command(&["rebase", "--continue"]).output()?;
 
         if !output.status.success() {
             let error_msg = String::from_utf8_lossy(&output.stderr);
@@ -85,24 +608,31 @@ impl GitRepo {
         Ok(())
     }
 
-    pub fn add_all(&self) -> Result<()> {
-        let status = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["add", "-A"])
-            .status()?;
-            
+    fn rebase_abort(&self) -> Result<()> {
+        let output = self.command(&["rebase", "--abort"]).output()?;
+
+        if !output.status.success() {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            bail!("Git rebase --abort failed: {}", error_msg);
+        }
+        Ok(())
+    }
+
+    fn add_all(&self) -> Result<()> {
+        let status = self.command(&["add", "-A"]).status()?;
+
         if !status.success() {
             bail!("Failed to add files to git staging");
         }
         Ok(())
     }
 
-    pub fn commit(&self, message: &str) -> Result<()> {
+    fn commit(&self, message: &str) -> Result<()> {
         self.run_command(&["commit", "-m", message])?;
         Ok(())
     }
 
-    pub fn stash(&self, message: &str) -> Result<bool> {
+    fn stash(&self, message: &str) -> Result<bool> {
         if !self.has_changes()? {
             return Ok(false);
         }
@@ -112,26 +642,24 @@ impl GitRepo {
         Ok(true)
     }
 
-    pub fn stash_pop(&self) -> Result<bool> {
+    fn stash_pop(&self) -> Result<bool> {
         let list = self.get_stash_list()?;
-        
+
         if list.is_empty() {
             return Ok(true);
         }
 
-        let status = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["stash", "pop"])
+        let status = self
+            .command(&["stash", "pop"])
             .status()
             .context("Failed to pop stash")?;
 
         Ok(status.success())
     }
 
-    pub fn get_stash_list(&self) -> Result<Vec<String>> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["stash", "list"])
+    fn get_stash_list(&self) -> Result<Vec<String>> {
+        let output = self
+            .command(&["stash", "list"])
             .output()
             .context("Failed to list stashes")?;
 
@@ -143,49 +671,80 @@ impl GitRepo {
         Ok(list)
     }
 
-    pub fn pull(&self, remote: &str, branch: &str) -> Result<()> {
+    fn pull(&self, remote: &str, branch: &str) -> Result<()> {
         self.run_command(&["pull", remote, branch])?;
         Ok(())
     }
 
-    pub fn pull_rebase(&self, remote: &str, branch: &str) -> Result<()> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["pull", "--rebase", remote, branch])
-            .output()?;
+    fn pull_rebase(&self, remote: &str, branch: &str) -> Result<()> {
+        self.pull_rebase_with_progress(remote, branch, &mut |_| {})
+    }
 
-        if !output.status.success() {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            bail!("Git rebase failed: {}", error_msg);
-        }
-        Ok(())
+    fn pull_rebase_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.run_command_with_progress(
+            &["pull", "--rebase", "--progress", remote, branch],
+            on_progress,
+        )
     }
 
-    pub fn push(&self, remote: &str, branch: &str) -> Result<()> {
-        self.run_command(&["push", remote, branch])?;
-        Ok(())
+    fn push(&self, remote: &str, branch: &str) -> Result<()> {
+        self.push_with_progress(remote, branch, &mut |_| {})
     }
 
-    pub fn push_set_upstream(&self, remote: &str, branch: &str) -> Result<()> {
-        self.run_command(&["push", "-u", remote, branch])?;
-        Ok(())
+    fn push_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.run_command_with_progress(&["push", "--progress", remote, branch], on_progress)
     }
 
-    pub fn has_remote(&self) -> Result<bool> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["remote"])
+    fn push_set_upstream(&self, remote: &str, branch: &str) -> Result<()> {
+        self.push_set_upstream_with_progress(remote, branch, &mut |_| {})
+    }
+
+    fn push_set_upstream_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.run_command_with_progress(&["push", "--progress", "-u", remote, branch], on_progress)
+    }
+
+    fn has_remote(&self) -> Result<bool> {
+        let output = self
+            .command(&["remote"])
             .output()
             .context("Failed to check remotes")?;
 
         Ok(!output.stdout.is_empty())
     }
 
-    pub fn remote_has_commits(&self, remote: &str, branch: &str) -> Result<bool> {
+    fn add_remote(&self, name: &str, url: &str) -> Result<()> {
+        let output = self
+            .command(&["remote", "add", name, url])
+            .output()
+            .context("Failed to add remote")?;
+
+        if !output.status.success() {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            bail!("Failed to add remote '{name}': {error_msg}");
+        }
+
+        Ok(())
+    }
+
+    fn remote_has_commits(&self, remote: &str, branch: &str) -> Result<bool> {
         // Check if remote has the branch by doing ls-remote
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["ls-remote", "--heads", remote, branch])
+        let output = self
+            .command(&["ls-remote", "--heads", remote, branch])
             .output()
             .context("Failed to check remote refs")?;
 
@@ -193,25 +752,23 @@ impl GitRepo {
         Ok(!output.stdout.is_empty())
     }
 
-    pub fn get_current_branch(&self) -> Result<String> {
-        let output = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(&["branch", "--show-current"])
+    fn get_current_branch(&self) -> Result<String> {
+        let output = self
+            .command(&["branch", "--show-current"])
             .output()
             .context("Failed to get current branch")?;
 
         let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        
+
         if branch.is_empty() {
             // In detached HEAD or old git, try to get branch from symbolic-ref
-            let output = Command::new("git")
-                .current_dir(&self.repo_path)
-                .args(&["symbolic-ref", "--short", "HEAD"])
+            let output = self
+                .command(&["symbolic-ref", "--short", "HEAD"])
                 .output()
                 .context("Failed to get symbolic ref")?;
-            
+
             let symbolic_branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            
+
             if !symbolic_branch.is_empty() {
                 Ok(symbolic_branch)
             } else {
@@ -222,17 +779,504 @@ impl GitRepo {
         }
     }
 
-    fn run_command(&self, args: &[&str]) -> Result<()> {
-        let status = Command::new("git")
-            .current_dir(&self.repo_path)
-            .args(args)
+    fn get_file_version(&self, path: &str, stage: u8) -> Result<Vec<u8>> {
+        let target_stage = match stage {
+            1 => gix::index::entry::Stage::Base,
+            2 => gix::index::entry::Stage::Ours,
+            3 => gix::index::entry::Stage::Theirs,
+            _ => bail!("Unsupported conflict stage {stage}"),
+        };
+
+        let repo = self.gix_repo()?;
+        let index = repo.index_or_empty().context("Failed to read git index")?;
+
+        for entry in index.entries() {
+            if entry.stage() == target_stage && entry.path(&index) == path {
+                let object = repo
+                    .find_object(entry.id)
+                    .context("Failed to read blob from git object database")?;
+                return Ok(object.data.to_vec());
+            }
+        }
+
+        bail!("No version of {path} at stage {stage}")
+    }
+
+    fn create_bundle(&self, dest: &Path, branch: &str) -> Result<()> {
+        self.run_command(&["bundle", "create", &dest.to_string_lossy(), branch])?;
+        Ok(())
+    }
+
+    fn import_bundle(&self, bundle: &Path, branch: &str) -> Result<()> {
+        let bundle_str = bundle.to_string_lossy();
+        let output = self
+            .command(&["bundle", "verify", &bundle_str])
+            .output()
+            .context("Failed to verify git bundle")?;
+
+        if !output.status.success() {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            bail!("Bundle verification failed: {}", error_msg);
+        }
+
+        self.run_command(&["fetch", &bundle_str, branch])?;
+
+        let fetch_head_output = self
+            .command(&["rev-parse", "FETCH_HEAD"])
+            .output()
+            .context("Failed to resolve FETCH_HEAD after fetching bundle")?;
+        let fetch_head = String::from_utf8_lossy(&fetch_head_output.stdout)
+            .trim()
+            .to_string();
+
+        let merge_base_output = self
+            .command(&["merge-base", "--is-ancestor", "HEAD", &fetch_head])
+            .output()
+            .context("Failed to check whether the bundle fast-forwards")?;
+
+        if merge_base_output.status.success() {
+            self.run_command(&["merge", "--ff-only", &fetch_head])?;
+            Ok(())
+        } else {
+            bail!("Importing this bundle would require a non-fast-forward merge; resolve manually")
+        }
+    }
+}
+
+/// Talks to the repository in-process via `git2` (libgit2), avoiding a subprocess
+/// spawn per call and giving typed errors instead of parsed stderr. The one exception
+/// is `create_bundle`/`import_bundle`, which shell out to `git` (libgit2 has no bundle
+/// API) and so carry `harden` the same as `ShellGitBackend` does.
+struct Git2Backend {
+    repo_path: Box<Path>,
+    harden: bool,
+}
+
+impl Git2Backend {
+    fn new(path: &Path, harden: bool) -> Self {
+        Self {
+            repo_path: path.into(),
+            harden,
+        }
+    }
+
+    fn open(&self) -> Result<git2::Repository> {
+        git2::Repository::open(&self.repo_path).context("Failed to open git repository")
+    }
+
+    /// Wires up credential resolution for a `fetch`/`push`/`connect`: ssh-agent first,
+    /// then `~/.ssh/id_*` keys, then a config/env token for HTTPS remotes. Tried in
+    /// that order per `allowed_types` challenge, falling back to `Cred::default()`
+    /// (matches anonymous/no-auth) if nothing applies.
+    fn configure_auth(&self, callbacks: &mut git2::RemoteCallbacks) {
+        let token = resolve_git_token(&self.repo_path);
+
+        callbacks.credentials(move |_url, username_from_url, allowed_types| {
+            let username = username_from_url.unwrap_or("git");
+
+            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+                if let Ok(cred) = git2::Cred::ssh_key_from_agent(username) {
+                    return Ok(cred);
+                }
+
+                if let Some(home) = dirs::home_dir() {
+                    for key_name in ["id_ed25519", "id_rsa", "id_ecdsa"] {
+                        let private_key = home.join(".ssh").join(key_name);
+                        if private_key.exists() {
+                            if let Ok(cred) = git2::Cred::ssh_key(username, None, &private_key, None) {
+                                return Ok(cred);
+                            }
+                        }
+                    }
+                }
+            }
+
+            if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(ref token) = token {
+                    return git2::Cred::userpass_plaintext(token, "");
+                }
+            }
+
+            git2::Cred::default()
+        });
+    }
+}
+
+impl GitBackend for Git2Backend {
+    fn is_repo(&self) -> bool {
+        git2::Repository::open(&self.repo_path).is_ok()
+    }
+
+    fn init(&self) -> Result<()> {
+        git2::Repository::init(&self.repo_path).context("Failed to initialize git repository")?;
+        Ok(())
+    }
+
+    fn has_changes(&self) -> Result<bool> {
+        let repo = self.open()?;
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        let statuses = repo.statuses(Some(&mut opts))?;
+        Ok(!statuses.is_empty())
+    }
+
+    fn is_in_merge(&self) -> Result<bool> {
+        let repo = self.open()?;
+        Ok(repo.state() == git2::RepositoryState::Merge)
+    }
+
+    fn is_in_rebase(&self) -> Result<bool> {
+        let repo = self.open()?;
+        Ok(matches!(
+            repo.state(),
+            git2::RepositoryState::Rebase
+                | git2::RepositoryState::RebaseInteractive
+                | git2::RepositoryState::RebaseMerge
+        ))
+    }
+
+    fn has_conflicts(&self) -> Result<bool> {
+        let repo = self.open()?;
+        let index = repo.index()?;
+        Ok(index.has_conflicts())
+    }
+
+    fn get_conflicted_files(&self) -> Result<Vec<String>> {
+        let repo = self.open()?;
+        let index = repo.index()?;
+        let mut files = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                if let Ok(path) = String::from_utf8(entry.path) {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    fn rebase_continue(&self) -> Result<()> {
+        let repo = self.open()?;
+        let signature = repo.signature()?;
+        let mut rebase = repo.open_rebase(None).context("No rebase in progress")?;
+        while let Some(op) = rebase.next() {
+            op?;
+            if let Err(e) = rebase.commit(None, &signature, None) {
+                if e.code() != git2::ErrorCode::Unmodified {
+                    return Err(e).context("Failed to continue rebase");
+                }
+            }
+        }
+        rebase.finish(Some(&signature))?;
+        Ok(())
+    }
+
+    fn rebase_abort(&self) -> Result<()> {
+        let repo = self.open()?;
+        let mut rebase = repo.open_rebase(None).context("No rebase in progress")?;
+        rebase.abort()?;
+        Ok(())
+    }
+
+    fn add_all(&self) -> Result<()> {
+        let repo = self.open()?;
+        let mut index = repo.index()?;
+        index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)?;
+        index.write()?;
+        Ok(())
+    }
+
+    fn commit(&self, message: &str) -> Result<()> {
+        let repo = self.open()?;
+        let signature = repo.signature()?;
+        let mut index = repo.index()?;
+        let tree_id = index.write_tree()?;
+        let tree = repo.find_tree(tree_id)?;
+
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?;
+        Ok(())
+    }
+
+    fn stash(&self, message: &str) -> Result<bool> {
+        if !self.has_changes()? {
+            return Ok(false);
+        }
+
+        let mut repo = self.open()?;
+        let signature = repo.signature()?;
+        repo.stash_save(
+            &signature,
+            message,
+            Some(git2::StashFlags::INCLUDE_UNTRACKED),
+        )?;
+        Ok(true)
+    }
+
+    fn stash_pop(&self) -> Result<bool> {
+        let mut repo = self.open()?;
+        match repo.stash_pop(0, None) {
+            Ok(()) => Ok(true),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(true),
+            Err(e) => Err(e).context("Failed to pop stash"),
+        }
+    }
+
+    fn get_stash_list(&self) -> Result<Vec<String>> {
+        let mut repo = self.open()?;
+        let mut list = Vec::new();
+        repo.stash_foreach(|_, message, _| {
+            list.push(message.to_string());
+            true
+        })?;
+        Ok(list)
+    }
+
+    fn pull(&self, remote: &str, branch: &str) -> Result<()> {
+        self.pull_rebase(remote, branch)
+    }
+
+    fn pull_rebase(&self, remote: &str, branch: &str) -> Result<()> {
+        self.pull_rebase_with_progress(remote, branch, &mut |_| {})
+    }
+
+    fn pull_rebase_with_progress(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        let repo = self.open()?;
+        let mut remote = repo.find_remote(remote_name)?;
+
+        let on_progress = std::cell::RefCell::new(on_progress);
+        let mut callbacks = git2::RemoteCallbacks::new();
+        self.configure_auth(&mut callbacks);
+        callbacks.transfer_progress(|stats| {
+            (on_progress.borrow_mut())(PushProgress::Transfer {
+                objects: stats.received_objects(),
+                total_objects: stats.total_objects(),
+            });
+            true
+        });
+        callbacks.update_tips(|refname, old, new| {
+            (on_progress.borrow_mut())(PushProgress::UpdateTips {
+                refname: refname.to_string(),
+                old_oid: old.to_string(),
+                new_oid: new.to_string(),
+            });
+            true
+        });
+
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        remote
+            .fetch(&[branch], Some(&mut fetch_options), None)
+            .context("Failed to fetch from remote")?;
+
+        let fetch_head = repo.find_reference("FETCH_HEAD")?;
+        let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+        let analysis = repo.merge_analysis(&[&fetch_commit])?;
+
+        if analysis.0.is_up_to_date() {
+            return Ok(());
+        }
+
+        if analysis.0.is_fast_forward() {
+            let refname = format!("refs/heads/{}", branch);
+            let mut reference = repo.find_reference(&refname)?;
+            reference.set_target(fetch_commit.id(), "Fast-forward")?;
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+            Ok(())
+        } else {
+            bail!("Pull requires a rebase/merge that diverges from upstream; resolve manually")
+        }
+    }
+
+    fn push(&self, remote: &str, branch: &str) -> Result<()> {
+        self.push_with_progress(remote, branch, &mut |_| {})
+    }
+
+    fn push_with_progress(
+        &self,
+        remote_name: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        let repo = self.open()?;
+        let mut remote = repo.find_remote(remote_name)?;
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+
+        let on_progress = std::cell::RefCell::new(on_progress);
+        let mut callbacks = git2::RemoteCallbacks::new();
+        self.configure_auth(&mut callbacks);
+        callbacks.push_transfer_progress(|current, total, bytes| {
+            (on_progress.borrow_mut())(PushProgress::PushTransfer {
+                current,
+                total,
+                bytes,
+            });
+        });
+        callbacks.push_update_reference(|refname, status| {
+            if status.is_none() {
+                (on_progress.borrow_mut())(PushProgress::UpdateTips {
+                    refname: refname.to_string(),
+                    old_oid: String::new(),
+                    new_oid: String::new(),
+                });
+            }
+            Ok(())
+        });
+
+        let mut push_options = git2::PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote
+            .push(&[&refspec], Some(&mut push_options))
+            .context("Failed to push to remote")?;
+        Ok(())
+    }
+
+    fn push_set_upstream(&self, remote: &str, branch: &str) -> Result<()> {
+        self.push_set_upstream_with_progress(remote, branch, &mut |_| {})
+    }
+
+    fn push_set_upstream_with_progress(
+        &self,
+        remote: &str,
+        branch: &str,
+        on_progress: &mut ProgressCallback,
+    ) -> Result<()> {
+        self.push_with_progress(remote, branch, on_progress)?;
+
+        let repo = self.open()?;
+        let mut local_branch = repo
+            .find_branch(branch, git2::BranchType::Local)
+            .context("Failed to look up local branch after push")?;
+        local_branch
+            .set_upstream(Some(&format!("{remote}/{branch}")))
+            .context("Failed to set upstream tracking branch")?;
+        Ok(())
+    }
+
+    fn has_remote(&self) -> Result<bool> {
+        let repo = self.open()?;
+        Ok(!repo.remotes()?.is_empty())
+    }
+
+    fn add_remote(&self, name: &str, url: &str) -> Result<()> {
+        let repo = self.open()?;
+        repo.remote(name, url).context("Failed to add remote")?;
+        Ok(())
+    }
+
+    fn remote_has_commits(&self, remote_name: &str, branch: &str) -> Result<bool> {
+        let repo = self.open()?;
+        let mut remote = repo.find_remote(remote_name)?;
+        let refname = format!("refs/heads/{branch}");
+
+        let mut callbacks = git2::RemoteCallbacks::new();
+        self.configure_auth(&mut callbacks);
+
+        let mut found = false;
+        remote.connect_auth(git2::Direction::Fetch, Some(callbacks), None)?;
+        for head in remote.list()? {
+            if head.name() == refname {
+                found = true;
+                break;
+            }
+        }
+        remote.disconnect()?;
+        Ok(found)
+    }
+
+    fn get_current_branch(&self) -> Result<String> {
+        let repo = self.open()?;
+        let head = repo.head().context("Unable to determine current branch. Make sure you're on a branch, not in detached HEAD state.")?;
+        head.shorthand()
+            .map(|s| s.to_string())
+            .ok_or_else(|| anyhow::anyhow!("Unable to determine current branch"))
+    }
+
+    fn get_file_version(&self, path: &str, stage: u8) -> Result<Vec<u8>> {
+        let repo = self.open()?;
+        let index = repo.index()?;
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = match stage {
+                1 => conflict.ancestor,
+                2 => conflict.our,
+                3 => conflict.their,
+                _ => None,
+            };
+            if let Some(entry) = entry {
+                if entry.path == path.as_bytes() {
+                    let blob = repo.find_blob(entry.id)?;
+                    return Ok(blob.content().to_vec());
+                }
+            }
+        }
+        bail!("No version of {path} at stage {stage}")
+    }
+
+    // `git2` has no bundle API; bundles are a porcelain-level feature implemented in
+    // `git`'s own CLI, not libgit2. Shell out for just these two operations rather than
+    // reimplementing the bundle file format, through the same `hardened_command` the
+    // shell backend uses so these aren't an unguarded path against an untrusted repo's
+    // `.git/config`.
+    fn create_bundle(&self, dest: &Path, branch: &str) -> Result<()> {
+        let status = hardened_command(&self.repo_path, &["bundle", "create"], self.harden)
+            .arg(dest)
+            .arg(branch)
             .status()
-            .context(format!("Failed to execute git {:?}", args))?;
+            .context("Failed to execute git bundle create")?;
 
         if !status.success() {
-            bail!("Git command failed: {:?}", args);
+            bail!("git bundle create failed");
         }
-
         Ok(())
     }
+
+    fn import_bundle(&self, bundle: &Path, branch: &str) -> Result<()> {
+        let status = hardened_command(&self.repo_path, &["bundle", "verify"], self.harden)
+            .arg(bundle)
+            .status()
+            .context("Failed to execute git bundle verify")?;
+        if !status.success() {
+            bail!("Bundle verification failed");
+        }
+
+        let repo = self.open()?;
+        let mut remote = repo.remote_anonymous(&bundle.to_string_lossy())?;
+        remote
+            .fetch(&[branch], None, None)
+            .context("Failed to fetch from bundle")?;
+
+        let fetch_head = repo.find_reference("FETCH_HEAD")?;
+        let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+        let analysis = repo.merge_analysis(&[&fetch_commit])?;
+
+        if analysis.0.is_up_to_date() {
+            Ok(())
+        } else if analysis.0.is_fast_forward() {
+            let refname = format!("refs/heads/{branch}");
+            let mut reference = repo.find_reference(&refname)?;
+            reference.set_target(fetch_commit.id(), "Fast-forward (bundle import)")?;
+            repo.set_head(&refname)?;
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+            Ok(())
+        } else {
+            bail!("Importing this bundle would require a non-fast-forward merge; resolve manually")
+        }
+    }
 }
@@ -0,0 +1,107 @@
+use anyhow::{Context, Result};
+use git2::{Repository, StatusOptions};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Where a single repo-relative path stands against git, independent of whether its
+/// home copy matches the repo copy on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitFileState {
+    /// Matches HEAD; no staged or unstaged changes.
+    Committed,
+    /// Differs from HEAD (staged, unstaged, or both).
+    Modified,
+    /// Matched by `.gitignore` — git will never pick up changes to this path.
+    Ignored,
+    /// Not tracked by git and not ignored (e.g. just copied into the repo, not yet added).
+    Untracked,
+}
+
+/// A snapshot of `git status` plus ahead/behind-upstream info, computed once per `scan`
+/// run so every stub's sync check can fold git reality into on-disk byte comparison
+/// without reopening the repository or re-running `git status` per stub.
+pub struct RepoStatus {
+    states: HashMap<String, GitFileState>,
+    pub ahead_behind: Option<(usize, usize)>,
+    pub has_commits: bool,
+}
+
+impl RepoStatus {
+    pub fn open(repo_path: &Path) -> Result<Self> {
+        let repo = Repository::open(repo_path).context("Failed to open git repository")?;
+
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .include_ignored(true)
+            .recurse_untracked_dirs(true)
+            .recurse_ignored_dirs(true);
+        let statuses = repo.statuses(Some(&mut opts))?;
+
+        let mut states = HashMap::new();
+        for entry in statuses.iter() {
+            let Some(path) = entry.path() else { continue };
+            states.insert(path.to_string(), classify(entry.status()));
+        }
+
+        let has_commits = repo.head().is_ok();
+        let ahead_behind = if has_commits { Self::compute_ahead_behind(&repo).unwrap_or(None) } else { None };
+
+        Ok(Self { states, ahead_behind, has_commits })
+    }
+
+    /// Ahead/behind counts against the current branch's upstream. `None` if the repo
+    /// has no commits yet, HEAD is detached, or the branch has no upstream configured —
+    /// in all of those cases "ahead/behind" isn't a meaningful question.
+    fn compute_ahead_behind(repo: &Repository) -> Result<Option<(usize, usize)>> {
+        let head = repo.head().context("no HEAD")?;
+        if !head.is_branch() {
+            return Ok(None);
+        }
+
+        let local_oid = match head.target() {
+            Some(oid) => oid,
+            None => return Ok(None),
+        };
+
+        let branch = git2::Branch::wrap(head);
+        let upstream = match branch.upstream() {
+            Ok(u) => u,
+            Err(_) => return Ok(None),
+        };
+        let upstream_oid = match upstream.get().target() {
+            Some(oid) => oid,
+            None => return Ok(None),
+        };
+
+        let (ahead, behind) = repo.graph_ahead_behind(local_oid, upstream_oid)?;
+        Ok(Some((ahead, behind)))
+    }
+
+    /// Status for a path relative to the repo root (forward-slash separated, matching
+    /// `git2`'s own path format). Paths git has never seen report as `Untracked`.
+    pub fn file_state(&self, relative_path: &str) -> GitFileState {
+        self.states.get(relative_path).copied().unwrap_or(GitFileState::Untracked)
+    }
+}
+
+fn classify(flags: git2::Status) -> GitFileState {
+    if flags.is_ignored() {
+        GitFileState::Ignored
+    } else if flags.is_wt_new() {
+        GitFileState::Untracked
+    } else if flags.intersects(
+        git2::Status::INDEX_NEW
+            | git2::Status::INDEX_MODIFIED
+            | git2::Status::INDEX_DELETED
+            | git2::Status::INDEX_RENAMED
+            | git2::Status::INDEX_TYPECHANGE
+            | git2::Status::WT_MODIFIED
+            | git2::Status::WT_DELETED
+            | git2::Status::WT_RENAMED
+            | git2::Status::WT_TYPECHANGE,
+    ) {
+        GitFileState::Modified
+    } else {
+        GitFileState::Committed
+    }
+}
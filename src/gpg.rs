@@ -0,0 +1,96 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::encryption::Encryptor;
+
+/// First byte of a GPG-backed `.enc` file, distinguishing it from `FileEncryptor`'s
+/// own `FORMAT_MAGIC` (`0xD0`) the same way that header already distinguishes its own
+/// format versions — so `decrypt_file` fails fast with a clear error instead of
+/// silently misinterpreting a file encrypted under the other backend.
+const GPG_BACKEND_TAG: u8 = 0xD1;
+
+/// `Encryptor` backend that shells out to `gpg`, encrypting to one or more configured
+/// public-key recipients instead of a shared symmetric key. Several machines or
+/// collaborators can each decrypt with their own private key, with no seed phrase to
+/// transport or re-wrap per recipient (see `FileEncryptor::add_recipient` for that
+/// alternative on the mnemonic backend).
+pub struct GpgEncryptor {
+    pub recipients: Vec<String>,
+}
+
+impl Encryptor for GpgEncryptor {
+    fn encrypt_file(&self, source: &Path, dest: &Path) -> Result<()> {
+        if self.recipients.is_empty() {
+            bail!("No GPG recipients configured; set gpg_recipients in local config");
+        }
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temp = dest.with_extension("gpg.tmp");
+
+        let mut cmd = Command::new("gpg");
+        cmd.args(["--batch", "--yes", "--trust-model", "always", "--output"]).arg(&temp);
+        cmd.arg("--encrypt");
+        for recipient in &self.recipients {
+            cmd.arg("--recipient").arg(recipient);
+        }
+        cmd.arg(source);
+
+        let status = cmd.status().context("Failed to run gpg --encrypt")?;
+        if !status.success() {
+            let _ = fs::remove_file(&temp);
+            bail!("gpg --encrypt failed for {}", source.display());
+        }
+
+        let ciphertext = fs::read(&temp).context("Failed to read gpg output")?;
+        let _ = fs::remove_file(&temp);
+
+        let mut out = Vec::with_capacity(1 + ciphertext.len());
+        out.push(GPG_BACKEND_TAG);
+        out.extend_from_slice(&ciphertext);
+        fs::write(dest, out).context("Failed to write GPG-encrypted file")?;
+
+        Ok(())
+    }
+
+    fn decrypt_file(&self, source: &Path, dest: &Path) -> Result<()> {
+        let data = fs::read(source).context("Failed to read encrypted file")?;
+
+        if data.first() != Some(&GPG_BACKEND_TAG) {
+            bail!(
+                "{} was not encrypted with the GPG backend (unrecognized header)",
+                source.display()
+            );
+        }
+
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let temp = source.with_extension("gpg.tmp");
+        fs::write(&temp, &data[1..])?;
+
+        let status = Command::new("gpg")
+            .args(["--batch", "--yes", "--output"])
+            .arg(dest)
+            .arg("--decrypt")
+            .arg(&temp)
+            .status()
+            .context("Failed to run gpg --decrypt");
+        let _ = fs::remove_file(&temp);
+
+        if !status?.success() {
+            bail!("gpg --decrypt failed for {}", source.display());
+        }
+
+        Ok(())
+    }
+
+    fn is_setup(&self, _repo_path: &Path) -> bool {
+        !self.recipients.is_empty()
+    }
+}
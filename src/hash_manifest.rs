@@ -0,0 +1,164 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const MANIFEST_FILE: &str = ".dotfiles.hashmanifest.json";
+
+/// Above this size, a content digest is split across fixed chunks so a future
+/// diff/resume feature can localize which region of a large file actually changed
+/// instead of only knowing "the whole file differs".
+const CHUNK_THRESHOLD: u64 = 4 * 1024 * 1024;
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Cheap fingerprint used to decide whether a file needs rehashing: if size and mtime
+/// both match the last time we hashed it, the cached digest is trusted without
+/// re-reading the file's contents.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+struct Fingerprint {
+    size: u64,
+    mtime_secs: u64,
+    mtime_nanos: u32,
+}
+
+impl Fingerprint {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::symlink_metadata(path).ok()?;
+        let mtime = metadata.modified().ok()?;
+        let since_epoch = mtime.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            mtime_secs: since_epoch.as_secs(),
+            mtime_nanos: since_epoch.subsec_nanos(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    fingerprint: Fingerprint,
+    /// Hex-encoded SHA-256 of the file's contents, or of the link target string for a
+    /// symlink — not of whatever the target points to, since a symlink whose target
+    /// changed is itself a real content change.
+    digest: String,
+    /// Per-chunk digests for files over `CHUNK_THRESHOLD`. Not consulted for the sync
+    /// decision today, just cached so a future "which region changed" feature doesn't
+    /// need a second full pass over large files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    chunk_digests: Option<Vec<String>>,
+}
+
+/// A cached map from a manifest key (e.g. `"home:~/.vimrc"` or `"repo:.vimrc"`) to the
+/// last-known fingerprint and content digest for that path, persisted in the repo so
+/// repeat scans don't re-read every tracked file's bytes. Falls back to full hashing
+/// transparently whenever the manifest is missing, corrupt, or simply doesn't have an
+/// entry yet — it's a cache, never a source of truth.
+///
+/// See `crate::sync_cache::SyncCache` for why encrypted-file sync comparisons use a
+/// separate cache instead of being layered on this one: this struct only ever answers
+/// "what's this one path's digest", with no encryption-key-aware invalidation or
+/// pairwise "are these two in sync" verdict.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HashManifest {
+    entries: HashMap<String, ManifestEntry>,
+}
+
+impl HashManifest {
+    fn manifest_path(repo_path: &Path) -> PathBuf {
+        repo_path.join(MANIFEST_FILE)
+    }
+
+    /// Loads the manifest, or starts a fresh empty one if it's missing or fails to
+    /// parse. A corrupt manifest just costs one full rehash of everything touched this
+    /// run, same as a cold cache.
+    pub fn load(repo_path: &Path) -> Self {
+        fs::read(Self::manifest_path(repo_path))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, repo_path: &Path) -> Result<()> {
+        let contents = serde_json::to_vec_pretty(self).context("Failed to serialize hash manifest")?;
+        fs::write(Self::manifest_path(repo_path), contents).context("Failed to write hash manifest")
+    }
+
+    /// Returns the content digest for `path`, hashing from scratch only if the cached
+    /// fingerprint no longer matches the file's current size/mtime (e.g. because a
+    /// `pull` brought in a newer commit and bumped the file's mtime past what's cached).
+    /// Returns `None` if `path` doesn't exist or can't be read.
+    pub fn digest_for(&mut self, key: &str, path: &Path) -> Option<String> {
+        let fingerprint = Fingerprint::of(path)?;
+
+        if let Some(entry) = self.entries.get(key) {
+            if entry.fingerprint == fingerprint {
+                return Some(entry.digest.clone());
+            }
+        }
+
+        let (digest, chunk_digests) = hash_path(path, fingerprint.size)?;
+        self.entries.insert(
+            key.to_string(),
+            ManifestEntry { fingerprint, digest: digest.clone(), chunk_digests },
+        );
+        Some(digest)
+    }
+}
+
+fn hash_path(path: &Path, size: u64) -> Option<(String, Option<Vec<String>>)> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(path).ok()?;
+        let mut hasher = Sha256::new();
+        hasher.update(target.to_string_lossy().as_bytes());
+        return Some((hex(&hasher.finalize()), None));
+    }
+
+    let mut file = fs::File::open(path).ok()?;
+    let mut whole_hasher = Sha256::new();
+    let mut chunk_digests = if size > CHUNK_THRESHOLD { Some(Vec::new()) } else { None };
+    let mut chunk_hasher = Sha256::new();
+    let mut chunk_read = 0usize;
+    let mut buf = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buf).ok()?;
+        if n == 0 {
+            break;
+        }
+
+        whole_hasher.update(&buf[..n]);
+
+        if let Some(chunks) = chunk_digests.as_mut() {
+            let mut offset = 0;
+            while offset < n {
+                let take = (CHUNK_SIZE - chunk_read).min(n - offset);
+                chunk_hasher.update(&buf[offset..offset + take]);
+                chunk_read += take;
+                offset += take;
+
+                if chunk_read == CHUNK_SIZE {
+                    chunks.push(hex(&std::mem::replace(&mut chunk_hasher, Sha256::new()).finalize()));
+                    chunk_read = 0;
+                }
+            }
+        }
+    }
+
+    if let Some(chunks) = chunk_digests.as_mut() {
+        if chunk_read > 0 {
+            chunks.push(hex(&chunk_hasher.finalize()));
+        }
+    }
+
+    Some((hex(&whole_hasher.finalize()), chunk_digests))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,38 @@
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+use crate::config_source::{import_cfg_dir, ConfigSource, ImportStats};
+
+/// Reads application definitions from a directory already on disk — a local mackup
+/// checkout, a vendored copy, anything with `applications/*.cfg` files in mackup's
+/// dialect — without the network round-trip `MackupSource` needs.
+pub struct LocalSource {
+    path: Option<PathBuf>,
+}
+
+impl LocalSource {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
+
+impl ConfigSource for LocalSource {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn fetch(&self, _temp_dir: &Path) -> Result<PathBuf> {
+        let Some(path) = &self.path else {
+            bail!("The 'local' source requires --path <dir> pointing at a directory of .cfg files");
+        };
+
+        if !path.is_dir() {
+            bail!("Local source path is not a directory: {}", path.display());
+        }
+
+        Ok(path.clone())
+    }
+
+    fn import(&self, source_root: &Path, output_dir: &Path) -> Result<ImportStats> {
+        import_cfg_dir(source_root, output_dir)
+    }
+}
@@ -1,10 +1,24 @@
+mod backup;
 mod cli;
 mod commands;
+mod compare;
 mod config;
+mod config_source;
 mod db;
+mod dir_spec;
 mod encryption;
 mod git;
+mod git_status;
+mod gpg;
+mod hash_manifest;
+mod local_source;
+mod mackup;
+mod manifest;
+mod merge;
+mod merge_tool;
+mod mount;
 mod sync;
+mod sync_cache;
 mod utils;
 
 use anyhow::Result;
@@ -15,25 +29,39 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Init { path, tag, encryption_key_path } => commands::init::execute(path, tag, encryption_key_path)?,
-        Commands::Add { stubs, encrypt, password } => commands::add::execute(stubs, encrypt, password)?,
+        Commands::Init { path, tag, remote } => commands::init::execute(path, tag, remote)?,
+        Commands::Add { stubs, encrypt, password, include, exclude } => {
+            commands::add::execute(stubs, encrypt, password, include, exclude)?
+        }
         Commands::Remove { stub_or_path } => commands::remove::execute(stub_or_path)?,
         Commands::List { all, stubs } => commands::list::execute(all, stubs)?,
         Commands::Status => commands::status::execute()?,
-        Commands::Sync { dir, r#continue, encryption_key_path, password } => {
+        Commands::Sync { dir, r#continue, encryption_key_path, password, dry_run, checksum } => {
             if r#continue {
                 commands::sync_continue::execute()?
             } else {
-                commands::sync::execute(dir, encryption_key_path, password)?
+                commands::sync::execute_with_options(dir, encryption_key_path, password, dry_run, checksum)?
             }
         },
+        Commands::SyncAbort => commands::sync_abort::execute()?,
+        Commands::SyncStatus => commands::sync_status::execute()?,
         Commands::SyncLocal => commands::sync_local::execute()?,
+        Commands::Rollback { at } => commands::rollback::execute(at)?,
+        Commands::Unlock => commands::unlock::execute()?,
         Commands::Pull => commands::pull::execute()?,
         Commands::Push => commands::push::execute()?,
+        Commands::Remote { action } => commands::remote::execute(action)?,
         Commands::Create { stub, paths, tag } => commands::create::execute(stub, paths, tag)?,
-        Commands::Scan => commands::scan::execute()?,
+        Commands::Scan { watch } => commands::scan::execute(watch)?,
         Commands::Cd => commands::cd::execute()?,
-        Commands::Config { key, value } => commands::config::execute(key, value)?,
+        Commands::Config { action } => commands::config::execute(action)?,
+        Commands::Db { action } => commands::db::execute(action)?,
+        Commands::Mount { mountpoint } => commands::mount::execute(mountpoint)?,
+        Commands::Apply { stubs } => commands::apply::execute(stubs)?,
+        Commands::Export { file, sign } => commands::export::execute(file, sign)?,
+        Commands::Import { file, verify_signature } => commands::import::execute(file, verify_signature)?,
+        Commands::SyncDb { source, path, output } => commands::sync_db::execute(source, path, output)?,
+        Commands::Watch { interval } => commands::watch::execute(interval)?,
     }
 
     Ok(())
@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::encryption::FileEncryptor;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MANIFEST_FILE: &str = ".dotfiles.manifest.enc";
+// Domain-separates the name-obfuscation subkey from the master data-encryption key.
+const NAME_KEY_INFO: &[u8] = b"dotfiles-name-hmac-v1";
+
+/// Maps an opaque on-disk filename back to the real tracked path. Used when "encrypt
+/// names" mode is enabled so the repo tree doesn't leak which configs are tracked.
+/// The manifest itself is stored as an encrypted blob alongside the files it describes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NameManifest {
+    entries: HashMap<String, String>,
+}
+
+impl NameManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn manifest_path(repo_path: &Path) -> PathBuf {
+        repo_path.join(MANIFEST_FILE)
+    }
+
+    pub fn exists(repo_path: &Path) -> bool {
+        Self::manifest_path(repo_path).exists()
+    }
+
+    /// Derive the HMAC subkey used to obfuscate names. Kept separate from the data
+    /// encryption key so the two can be reasoned about (and eventually rotated)
+    /// independently even though both ultimately come from the same master key.
+    pub fn derive_name_key(master_key: &[u8; 32]) -> [u8; 32] {
+        let mut mac = HmacSha256::new_from_slice(master_key).expect("HMAC accepts any key length");
+        mac.update(NAME_KEY_INFO);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&mac.finalize().into_bytes());
+        key
+    }
+
+    /// Deterministically obfuscate a normalized relative path (e.g. "~/.vimrc") into a
+    /// hex-encoded opaque name that is safe to use as a file name in the repo.
+    pub fn obfuscate(name_key: &[u8; 32], normalized_path: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(name_key).expect("HMAC accepts any key length");
+        mac.update(normalized_path.as_bytes());
+        hex_encode(&mac.finalize().into_bytes())
+    }
+
+    /// Load the manifest for a repo, decrypting it with the provided key. Returns an
+    /// empty manifest if none has been created yet (first file being added under
+    /// "encrypt names" mode).
+    pub fn load(repo_path: &Path, key: &[u8; 32]) -> Result<Self> {
+        let path = Self::manifest_path(repo_path);
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let encrypted = fs::read(&path).context("Failed to read name manifest")?;
+        let decrypted = FileEncryptor::decrypt_data(&encrypted, key)
+            .context("Failed to decrypt name manifest")?;
+        serde_json::from_slice(&decrypted).context("Failed to parse name manifest")
+    }
+
+    pub fn save(&self, repo_path: &Path, key: &[u8; 32]) -> Result<()> {
+        let path = Self::manifest_path(repo_path);
+        let plaintext = serde_json::to_vec(self).context("Failed to serialize name manifest")?;
+        let encrypted = FileEncryptor::encrypt_data(&plaintext, key)
+            .context("Failed to encrypt name manifest")?;
+        fs::write(&path, encrypted).context("Failed to write name manifest")?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, opaque_name: String, real_path: String) {
+        self.entries.insert(opaque_name, real_path);
+    }
+
+    pub fn resolve(&self, opaque_name: &str) -> Option<&str> {
+        self.entries.get(opaque_name).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter()
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
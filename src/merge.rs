@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+/// Outcome of a [`diff3_merge`]: the merged text, plus whether any hunk needed
+/// conflict markers.
+pub struct Merge {
+    pub text: String,
+    pub has_conflicts: bool,
+}
+
+/// Performs a diff3-style three-way line merge of `base`, `ours`, and `theirs`.
+/// Synchronizes on lines common to both the base→ours and base→theirs LCS
+/// alignments, emits unchanged regions verbatim, auto-resolves regions changed on
+/// only one side (or changed identically on both), and wraps only genuinely
+/// conflicting hunks in `<<<<<<< ours / ||||||| base / ======= / >>>>>>> theirs`
+/// markers.
+pub fn diff3_merge(base: &str, ours: &str, theirs: &str) -> Merge {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let ours_lines: Vec<&str> = ours.lines().collect();
+    let theirs_lines: Vec<&str> = theirs.lines().collect();
+
+    let ours_match: HashMap<usize, usize> = lcs_pairs(&base_lines, &ours_lines).into_iter().collect();
+    let theirs_match: HashMap<usize, usize> =
+        lcs_pairs(&base_lines, &theirs_lines).into_iter().collect();
+
+    let mut anchors: Vec<usize> = ours_match
+        .keys()
+        .filter(|i| theirs_match.contains_key(i))
+        .copied()
+        .collect();
+    anchors.sort_unstable();
+
+    let mut out = String::new();
+    let mut has_conflicts = false;
+
+    let mut base_pos = 0;
+    let mut ours_pos = 0;
+    let mut theirs_pos = 0;
+
+    for anchor in anchors {
+        let ours_anchor = ours_match[&anchor];
+        let theirs_anchor = theirs_match[&anchor];
+
+        has_conflicts |= merge_hunk(
+            &base_lines[base_pos..anchor],
+            &ours_lines[ours_pos..ours_anchor],
+            &theirs_lines[theirs_pos..theirs_anchor],
+            &mut out,
+        );
+
+        out.push_str(base_lines[anchor]);
+        out.push('\n');
+
+        base_pos = anchor + 1;
+        ours_pos = ours_anchor + 1;
+        theirs_pos = theirs_anchor + 1;
+    }
+
+    has_conflicts |= merge_hunk(
+        &base_lines[base_pos..],
+        &ours_lines[ours_pos..],
+        &theirs_lines[theirs_pos..],
+        &mut out,
+    );
+
+    Merge {
+        text: out,
+        has_conflicts,
+    }
+}
+
+/// Merges one hunk (the lines between two synchronization anchors, or before the
+/// first/after the last) and appends the result to `out`. Returns whether the hunk
+/// needed conflict markers.
+fn merge_hunk(base: &[&str], ours: &[&str], theirs: &[&str], out: &mut String) -> bool {
+    let ours_changed = ours != base;
+    let theirs_changed = theirs != base;
+
+    let resolved = if !ours_changed && !theirs_changed {
+        Some(base)
+    } else if ours_changed && !theirs_changed {
+        Some(ours)
+    } else if !ours_changed && theirs_changed {
+        Some(theirs)
+    } else if ours == theirs {
+        Some(ours)
+    } else {
+        None
+    };
+
+    if let Some(lines) = resolved {
+        for line in lines {
+            out.push_str(line);
+            out.push('\n');
+        }
+        return false;
+    }
+
+    out.push_str("<<<<<<< ours\n");
+    for line in ours {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("||||||| base\n");
+    for line in base {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("=======\n");
+    for line in theirs {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str(">>>>>>> theirs\n");
+    true
+}
+
+/// Longest-common-subsequence alignment between `a` and `b`, returned as matched
+/// index pairs `(a_index, b_index)` in increasing order.
+fn lcs_pairs(a: &[&str], b: &[&str]) -> Vec<(usize, usize)> {
+    let n = a.len();
+    let m = b.len();
+    let mut dp = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if a[i] == b[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_input_merges_clean() {
+        let base = "a\nb\nc\n";
+        let merge = diff3_merge(base, base, base);
+        assert!(!merge.has_conflicts);
+        assert_eq!(merge.text, base);
+    }
+
+    #[test]
+    fn change_on_one_side_only_auto_resolves() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nB\nc\n";
+        let merge = diff3_merge(base, ours, base);
+        assert!(!merge.has_conflicts);
+        assert_eq!(merge.text, ours);
+    }
+
+    #[test]
+    fn identical_change_on_both_sides_auto_resolves() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nB\nc\n";
+        let theirs = "a\nB\nc\n";
+        let merge = diff3_merge(base, ours, theirs);
+        assert!(!merge.has_conflicts);
+        assert_eq!(merge.text, ours);
+    }
+
+    #[test]
+    fn conflicting_changes_get_conflict_markers() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nOURS\nc\n";
+        let theirs = "a\nTHEIRS\nc\n";
+        let merge = diff3_merge(base, ours, theirs);
+        assert!(merge.has_conflicts);
+        assert_eq!(
+            merge.text,
+            "a\n<<<<<<< ours\nOURS\n||||||| base\nb\n=======\nTHEIRS\n>>>>>>> theirs\nc\n"
+        );
+    }
+
+    #[test]
+    fn additions_on_both_sides_interleave_without_conflict() {
+        let base = "a\nc\n";
+        let ours = "a\nb\nc\n";
+        let theirs = "a\nc\nd\n";
+        let merge = diff3_merge(base, ours, theirs);
+        assert!(!merge.has_conflicts);
+        assert_eq!(merge.text, "a\nb\nc\nd\n");
+    }
+}
@@ -0,0 +1,45 @@
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Runs a user-configured three-way merge tool command template (e.g.
+/// `"vimdiff $left $base $right -c 'wq $output'"`) against one conflict's three
+/// temp-file sides, blocking until the tool exits. Modeled on jujutsu's
+/// `merge_tools`: the template is whitespace-split into tokens, `$left`/`$base`/
+/// `$right`/`$output` are substituted with the given paths in every token, and the
+/// result is spawned directly (no shell) — the tool itself, not this function,
+/// owns any further quoting it needs.
+///
+/// A non-zero exit, or an output file whose mtime didn't change, is treated as
+/// "conflict left unresolved".
+pub fn run(template: &str, left: &Path, base: &Path, right: &Path, output: &Path) -> Result<()> {
+    let before = std::fs::metadata(output).and_then(|m| m.modified()).ok();
+
+    let mut tokens = template.split_whitespace().map(|token| substitute(token, left, base, right, output));
+    let program = tokens.next().context("merge_tool command is empty")?;
+    let args: Vec<String> = tokens.collect();
+
+    let status = Command::new(&program)
+        .args(&args)
+        .status()
+        .context(format!("Failed to launch merge tool: {program}"))?;
+
+    if !status.success() {
+        bail!("Merge tool exited with a non-zero status; conflict left unresolved");
+    }
+
+    let after = std::fs::metadata(output).and_then(|m| m.modified()).ok();
+    if before.is_some() && before == after {
+        bail!("Merge tool did not modify the output file; conflict left unresolved");
+    }
+
+    Ok(())
+}
+
+fn substitute(token: &str, left: &Path, base: &Path, right: &Path, output: &Path) -> String {
+    token
+        .replace("$left", &left.to_string_lossy())
+        .replace("$base", &base.to_string_lossy())
+        .replace("$right", &right.to_string_lossy())
+        .replace("$output", &output.to_string_lossy())
+}
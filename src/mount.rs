@@ -0,0 +1,205 @@
+use fuser::{FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, Request};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::config::TrackedFile;
+use crate::encryption::FileEncryptor;
+
+const TTL: Duration = Duration::from_secs(1);
+const ROOT_INO: u64 = 1;
+
+enum Node {
+    Dir { children: HashMap<String, u64> },
+    File { repo_path: PathBuf, encrypted: bool },
+}
+
+/// Read-only FUSE filesystem presenting tracked dotfiles decrypted on demand.
+/// Directory structure mirrors each `TrackedFile.path`'s `~/...` layout; encrypted
+/// files are decrypted lazily through the chunked AEAD reader, one touched chunk at a
+/// time, so plaintext is never written to disk. Unmounting leaves nothing behind.
+pub struct DotfilesFs {
+    nodes: HashMap<u64, Node>,
+    key: [u8; 32],
+}
+
+impl DotfilesFs {
+    pub fn new(tracked: &[TrackedFile], repo_path: &Path, key: [u8; 32]) -> Self {
+        let mut nodes = HashMap::new();
+        nodes.insert(ROOT_INO, Node::Dir { children: HashMap::new() });
+        let mut next_ino = ROOT_INO + 1;
+
+        for file in tracked {
+            let relative = file.path.trim_start_matches("~/").trim_start_matches('/');
+            let components: Vec<&str> = relative.split('/').filter(|c| !c.is_empty()).collect();
+            if components.is_empty() {
+                continue;
+            }
+
+            let mut parent = ROOT_INO;
+            for (i, component) in components.iter().enumerate() {
+                let is_leaf = i == components.len() - 1;
+
+                let existing = match nodes.get(&parent) {
+                    Some(Node::Dir { children }) => children.get(*component).copied(),
+                    _ => None,
+                };
+
+                let ino = existing.unwrap_or_else(|| {
+                    let ino = next_ino;
+                    next_ino += 1;
+
+                    let node = if is_leaf {
+                        let file_name = if file.encrypted {
+                            Path::new(component).with_extension("enc").to_string_lossy().into_owned()
+                        } else {
+                            component.to_string()
+                        };
+                        let mut leaf_path = repo_path.to_path_buf();
+                        for dir_component in &components[..components.len() - 1] {
+                            leaf_path.push(dir_component);
+                        }
+                        leaf_path.push(file_name);
+                        Node::File { repo_path: leaf_path, encrypted: file.encrypted }
+                    } else {
+                        Node::Dir { children: HashMap::new() }
+                    };
+                    nodes.insert(ino, node);
+
+                    if let Some(Node::Dir { children }) = nodes.get_mut(&parent) {
+                        children.insert(component.to_string(), ino);
+                    }
+
+                    ino
+                });
+
+                parent = ino;
+            }
+        }
+
+        Self { nodes, key }
+    }
+
+    fn attr_for(&self, ino: u64) -> Option<FileAttr> {
+        let node = self.nodes.get(&ino)?;
+        let size = match node {
+            Node::Dir { .. } => 0,
+            Node::File { repo_path, encrypted } => {
+                if *encrypted {
+                    FileEncryptor::plaintext_len(repo_path, &self.key).unwrap_or(0)
+                } else {
+                    fs::metadata(repo_path).map(|m| m.len()).unwrap_or(0)
+                }
+            }
+        };
+
+        Some(FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: UNIX_EPOCH,
+            mtime: UNIX_EPOCH,
+            ctime: UNIX_EPOCH,
+            crtime: UNIX_EPOCH,
+            kind: match node {
+                Node::Dir { .. } => FileType::Directory,
+                Node::File { .. } => FileType::RegularFile,
+            },
+            perm: match node {
+                Node::Dir { .. } => 0o555,
+                Node::File { .. } => 0o444,
+            },
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        })
+    }
+}
+
+impl Filesystem for DotfilesFs {
+    fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let name = match name.to_str() {
+            Some(name) => name,
+            None => return reply.error(libc::ENOENT),
+        };
+
+        let child_ino = match self.nodes.get(&parent) {
+            Some(Node::Dir { children }) => children.get(name).copied(),
+            _ => None,
+        };
+
+        match child_ino.and_then(|ino| self.attr_for(ino)) {
+            Some(attr) => reply.entry(&TTL, &attr, 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.attr_for(ino) {
+            Some(attr) => reply.attr(&TTL, &attr),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn read(&mut self, _req: &Request, ino: u64, _fh: u64, offset: i64, size: u32, _flags: i32, _lock_owner: Option<u64>, reply: ReplyData) {
+        let (repo_path, encrypted) = match self.nodes.get(&ino) {
+            Some(Node::File { repo_path, encrypted }) => (repo_path.clone(), *encrypted),
+            _ => return reply.error(libc::EISDIR),
+        };
+
+        let offset = offset.max(0) as u64;
+        let result = if encrypted {
+            FileEncryptor::read_plaintext_range(&repo_path, &self.key, offset, size as usize)
+        } else {
+            read_plain_range(&repo_path, offset, size as usize)
+        };
+
+        match result {
+            Ok(data) => reply.data(&data),
+            Err(_) => reply.error(libc::EIO),
+        }
+    }
+
+    fn readdir(&mut self, _req: &Request, ino: u64, _fh: u64, offset: i64, mut reply: ReplyDirectory) {
+        let children: Vec<(u64, String, FileType)> = match self.nodes.get(&ino) {
+            Some(Node::Dir { children }) => children
+                .iter()
+                .map(|(name, &child_ino)| {
+                    let kind = match self.nodes.get(&child_ino) {
+                        Some(Node::Dir { .. }) => FileType::Directory,
+                        _ => FileType::RegularFile,
+                    };
+                    (child_ino, name.clone(), kind)
+                })
+                .collect(),
+            _ => return reply.error(libc::ENOENT),
+        };
+
+        let mut entries = vec![(ino, FileType::Directory, ".".to_string()), (ino, FileType::Directory, "..".to_string())];
+        entries.extend(children.into_iter().map(|(ino, name, kind)| (ino, kind, name)));
+
+        for (i, (ino, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(ino, (i + 1) as i64, kind, name) {
+                break;
+            }
+        }
+
+        reply.ok();
+    }
+}
+
+fn read_plain_range(path: &Path, offset: u64, size: usize) -> anyhow::Result<Vec<u8>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = vec![0u8; size];
+    let n = file.read(&mut buf)?;
+    buf.truncate(n);
+    Ok(buf)
+}
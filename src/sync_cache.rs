@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const MANIFEST_FILE: &str = ".dotfiles.synccache.json";
+
+/// Cheap fingerprint of a file's current state. Two fingerprints being equal is treated
+/// as "the file hasn't changed" without re-reading its contents.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+struct Fingerprint {
+    mtime_ns: u128,
+    size: u64,
+}
+
+impl Fingerprint {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let mtime = metadata.modified().ok()?;
+        let since_epoch = mtime.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            mtime_ns: since_epoch.as_nanos(),
+            size: metadata.len(),
+        })
+    }
+}
+
+/// What's remembered about one tracked encrypted file the last time it was actually
+/// decrypted and compared: the home plaintext's fingerprint, the `.enc` file's
+/// fingerprint (so a pull that replaces the `.enc` invalidates the entry even if the
+/// home file itself hasn't moved), whether the two sides matched, and the decrypted
+/// plaintext's content hash (kept so a `--checksum` run can report it without a fresh
+/// decrypt when the cache already confirms a mismatch).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    home_fingerprint: Fingerprint,
+    enc_fingerprint: Fingerprint,
+    content_hash: String,
+    in_sync: bool,
+}
+
+/// Caches the outcome of decrypting and comparing each tracked encrypted file against
+/// its home counterpart, so a sync where nothing changed doesn't pay for a decrypt and
+/// a full read of every encrypted file. Persisted as a gitignored JSON file in the repo;
+/// a missing or corrupt manifest just costs one full rescan, same as `HashManifest`.
+///
+/// Deliberately a separate cache from `crate::hash_manifest::HashManifest` rather than
+/// built on top of it: this one caches a *comparison verdict* between two paths (keyed
+/// additionally by a fingerprint of the encryption key, so rotating keys invalidates
+/// everything at once), while `HashManifest` caches a single path's *content digest* in
+/// isolation and has no notion of "in sync with what". `sync`'s encrypted-file
+/// comparison could theoretically be rebuilt out of two `HashManifest` digest lookups
+/// plus a home-grown "do they match, and under which key" layer on top, but that layer
+/// is this struct — there's no meaningful amount of duplicated logic left to factor out
+/// by merging them, just the unavoidable fact that both are mtime+size-fingerprinted
+/// JSON caches for the same general reason (don't redo expensive I/O on unchanged
+/// files).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncCache {
+    /// Hex-encoded SHA-256 of the encryption key these entries were cached under. A key
+    /// change (restore from seed phrase, key rotation) invalidates every entry at once
+    /// rather than silently comparing plaintexts decrypted under different keys.
+    #[serde(default)]
+    key_fingerprint: Option<String>,
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl SyncCache {
+    fn manifest_path(repo_path: &Path) -> PathBuf {
+        repo_path.join(MANIFEST_FILE)
+    }
+
+    /// Loads the cache, discarding every entry if it's missing, corrupt, or was written
+    /// under a different encryption key than `key`.
+    pub fn load(repo_path: &Path, key: &[u8; 32]) -> Self {
+        let key_fingerprint = Some(hex(&Sha256::digest(key)));
+
+        let loaded: Self = fs::read(Self::manifest_path(repo_path))
+            .ok()
+            .and_then(|raw| serde_json::from_slice(&raw).ok())
+            .unwrap_or_default();
+
+        if loaded.key_fingerprint != key_fingerprint {
+            return Self {
+                key_fingerprint,
+                entries: HashMap::new(),
+            };
+        }
+        loaded
+    }
+
+    pub fn save(&self, repo_path: &Path) -> Result<()> {
+        let contents =
+            serde_json::to_vec_pretty(self).context("Failed to serialize sync cache")?;
+        fs::write(Self::manifest_path(repo_path), contents).context("Failed to write sync cache")
+    }
+
+    /// Returns `Some(in_sync)` if `home_path` and `enc_path` both still match the
+    /// fingerprints recorded the last time this entry was decrypted and compared, i.e.
+    /// the cached verdict can be trusted without touching the encrypted file at all.
+    /// Returns `None` on any miss (first sync, a changed home file, or a `.enc` that's
+    /// been replaced since), so the caller falls back to a real decrypt-and-compare.
+    pub fn check(&self, key: &str, home_path: &Path, enc_path: &Path) -> Option<bool> {
+        let entry = self.entries.get(key)?;
+        let home_fingerprint = Fingerprint::of(home_path)?;
+        let enc_fingerprint = Fingerprint::of(enc_path)?;
+
+        if home_fingerprint != entry.home_fingerprint || enc_fingerprint != entry.enc_fingerprint {
+            return None;
+        }
+
+        Some(entry.in_sync)
+    }
+
+    /// Looks up the content hash recorded for `key`, for printing alongside a
+    /// cache-confirmed mismatch in `--checksum` mode without re-decrypting.
+    pub fn content_hash(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|e| e.content_hash.as_str())
+    }
+
+    /// Records the outcome of a fresh decrypt-and-compare. Silently does nothing if
+    /// either path's metadata can't be read (e.g. it was removed mid-sync) — the next
+    /// run will simply treat it as a cache miss again.
+    pub fn record(
+        &mut self,
+        key: &str,
+        home_path: &Path,
+        enc_path: &Path,
+        content_hash: String,
+        in_sync: bool,
+    ) {
+        let (Some(home_fingerprint), Some(enc_fingerprint)) =
+            (Fingerprint::of(home_path), Fingerprint::of(enc_path))
+        else {
+            return;
+        };
+
+        self.entries.insert(
+            key.to_string(),
+            CacheEntry {
+                home_fingerprint,
+                enc_fingerprint,
+                content_hash,
+                in_sync,
+            },
+        );
+    }
+}
+
+fn hex(bytes: impl AsRef<[u8]>) -> String {
+    bytes
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}